@@ -0,0 +1,255 @@
+//! On-disk cache for slow, rarely changing modules
+//!
+//! Keeps the structured value of the CPU model, GPU list, host/DMI strings,
+//! displays and package counts in `$XDG_CACHE_HOME/neofetch/cache.json`, so
+//! repeated runs (e.g. from a shell startup file) skip their collectors.
+//!
+//! Every cached module has its own TTL. Hardware entries are also dropped when
+//! the boot ID changes, and package counts when the package database is modified.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Neofetch;
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// What makes a cached module go stale besides its TTL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Invalidation {
+    /// Hardware may change across reboots
+    Boot,
+    /// Package counts change when the package database is modified
+    PackageDatabase,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    value: Value,
+    collected_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boot_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    packages_mtime: Option<u64>,
+}
+
+/// Current system state that entries are validated against
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Stamp {
+    now: u64,
+    boot_id: Option<String>,
+    packages_mtime: Option<u64>,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Identifier of the current boot
+async fn boot_id() -> Option<String> {
+    if let Ok(id) = crate::utils::read_file_to_string("/proc/sys/kernel/random/boot_id").await {
+        return Some(id.trim().to_string());
+    }
+    // Elsewhere derive it from the boot time, rounded because now - uptime jitters
+    let uptime = crate::uptime::get_uptime().await.ok()?;
+    Some(format!("boot-{}", unix_now().saturating_sub(uptime.0) / 60))
+}
+
+/// Cached module values
+#[derive(Debug, Clone, Default)]
+pub struct Cache {
+    entries: BTreeMap<String, Entry>,
+    ttls: HashMap<Module, Duration>,
+    stamp: Stamp,
+    dirty: bool,
+}
+
+impl Cache {
+    /// Default TTL of a module; modules without one are never cached
+    pub fn default_ttl(module: Module) -> Option<Duration> {
+        let days = match module {
+            Module::Host | Module::Rom | Module::Baseband => 30,
+            Module::Cpu | Module::Gpu => 7,
+            Module::Display | Module::Packages => 1,
+            Module::Extra(name) => return crate::collector::get(name)?.ttl(),
+            _ => return None,
+        };
+        Some(Duration::from_secs(days * DAY))
+    }
+
+    fn invalidation(module: Module) -> Invalidation {
+        match module {
+            Module::Packages => Invalidation::PackageDatabase,
+            _ => Invalidation::Boot,
+        }
+    }
+
+    /// Location of the cache file
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::cache_dir()?.join("neofetch").join("cache.json"))
+    }
+
+    /// Load the cache file; a missing or unreadable file gives an empty cache
+    pub async fn open() -> Cache {
+        let entries = Cache::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Cache {
+            entries,
+            ttls: Module::all()
+                .into_iter()
+                .filter_map(|m| Some((m, Cache::default_ttl(m)?)))
+                .collect(),
+            stamp: Stamp {
+                now: unix_now(),
+                boot_id: boot_id().await,
+                packages_mtime: crate::packages::database_mtime(),
+            },
+            dirty: false,
+        }
+    }
+
+    /// Delete the cache file
+    pub fn clear() -> Result<()> {
+        let Some(path) = Cache::path() else {
+            return Ok(());
+        };
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(NeofetchError::file_write(path.display().to_string(), e))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Override the TTL of a module; `None` stops caching it
+    pub fn set_ttl(&mut self, module: Module, ttl: Option<Duration>) {
+        match ttl {
+            Some(ttl) => self.ttls.insert(module, ttl),
+            None => self.ttls.remove(&module),
+        };
+    }
+
+    fn is_valid(&self, module: Module, entry: &Entry) -> bool {
+        let Some(ttl) = self.ttls.get(&module) else {
+            return false;
+        };
+        let fresh = self.stamp.now.saturating_sub(entry.collected_at) < ttl.as_secs();
+        fresh
+            && match Cache::invalidation(module) {
+                Invalidation::Boot => entry.boot_id == self.stamp.boot_id,
+                Invalidation::PackageDatabase => entry.packages_mtime == self.stamp.packages_mtime,
+            }
+    }
+
+    /// Fill `neofetch` from valid entries and return the modules still to collect
+    pub fn load_into(&self, neofetch: &mut Neofetch, modules: &[Module]) -> Vec<Module> {
+        modules
+            .iter()
+            .copied()
+            .filter(|&module| match self.entries.get(module.name()) {
+                Some(entry) if self.is_valid(module, entry) => {
+                    neofetch.set_value(module, Ok(entry.value.clone()));
+                    false
+                }
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Record freshly collected modules; errors are not cached so they are retried
+    pub fn store(&mut self, neofetch: &Neofetch, modules: &[Module]) {
+        for &module in modules {
+            if !self.ttls.contains_key(&module) {
+                continue;
+            }
+            if let Ok(value) = neofetch.value(module) {
+                let entry = Entry {
+                    value,
+                    collected_at: self.stamp.now,
+                    boot_id: self.stamp.boot_id.clone(),
+                    packages_mtime: self.stamp.packages_mtime,
+                };
+                self.entries.insert(module.name().to_string(), entry);
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Write the cache file if anything was stored
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = Cache::path().filter(|_| self.dirty) else {
+            return Ok(());
+        };
+        let content = serde_json::to_string(&self.entries)
+            .map_err(|e| NeofetchError::serialization(e.to_string()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| NeofetchError::file_write(dir.display().to_string(), e))?;
+        }
+        std::fs::write(&path, content)
+            .map_err(|e| NeofetchError::file_write(path.display().to_string(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cache() -> Cache {
+        Cache {
+            ttls: [(Module::Cpu, Duration::from_secs(DAY))].into(),
+            stamp: Stamp {
+                now: 10 * DAY,
+                boot_id: Some("a".into()),
+                packages_mtime: Some(1),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cache_validation() {
+        let mut neofetch = Neofetch::empty();
+        neofetch.set_value(
+            Module::Cpu,
+            Ok(json!({ "name": "Ryzen", "cores": 8, "speed_mhz": 3000 })),
+        );
+        neofetch.set_value(Module::Kernel, Ok(json!("6.8.0")));
+
+        let mut cache = cache();
+        cache.store(&neofetch, &[Module::Cpu, Module::Kernel]);
+        assert_eq!(cache.entries.len(), 1);
+
+        let mut loaded = Neofetch::empty();
+        let pending = cache.load_into(&mut loaded, &[Module::Cpu, Module::Kernel]);
+        assert_eq!(pending, [Module::Kernel]);
+        assert_eq!(loaded.cpu.unwrap().name, "Ryzen");
+
+        // A reboot invalidates hardware entries
+        cache.stamp.boot_id = Some("b".into());
+        assert_eq!(
+            cache.load_into(&mut Neofetch::empty(), &[Module::Cpu]),
+            [Module::Cpu]
+        );
+
+        // So does the TTL
+        cache.stamp.boot_id = Some("a".into());
+        cache.stamp.now += 2 * DAY;
+        assert_eq!(
+            cache.load_into(&mut Neofetch::empty(), &[Module::Cpu]),
+            [Module::Cpu]
+        );
+    }
+}
@@ -0,0 +1,476 @@
+//! Assertions on the collected values, for CI and provisioning
+//!
+//! A rule compares a field of a module with a value, e.g.
+//! `memory.total_bytes >= 16G`, `kernel >= 6.1`, `os.distro == ubuntu` or
+//! `disk.percent <= 80`. Fields are those of the structured output plus the
+//! derived fields of [`crate::template`]; scalar modules such as `kernel` are
+//! compared without a field. A rule on a list module (disks, GPUs, sensors,
+//! ...) must hold for every item.
+//!
+//! The value is compared as a size (`16G`, `512MiB`), a percentage (`80%`), a
+//! number, a version (`6.1` against `6.8.0-45-generic`) or case-insensitive
+//! text. `~` tests whether the text contains the value.
+//!
+//! Rules come from the command line or a TOML file:
+//!
+//! ```toml
+//! min_memory = "16G"
+//! kernel = ">=6.1"
+//! distro = "ubuntu"
+//! max_disk_usage = "80%"
+//! rules = ["cpu.cores >= 8", "gpu.name ~ nvidia"]
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Neofetch;
+use crate::color::{BOLD, GREEN, RED, RESET};
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+use crate::output::{Format, serialize_list};
+use crate::redact::Scrubber;
+
+/// Comparison of a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+impl Op {
+    /// Operators by symbol, two-character ones first
+    const SYMBOLS: [(&'static str, Op); 8] = [
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        ("==", Op::Eq),
+        ("!=", Op::Ne),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+        ("=", Op::Eq),
+        ("~", Op::Contains),
+    ];
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Contains => "~",
+        }
+    }
+
+    /// Operator at the start of `s` and the rest
+    fn strip(s: &str) -> Option<(Op, &str)> {
+        Op::SYMBOLS
+            .iter()
+            .find_map(|(symbol, op)| Some((*op, s.strip_prefix(symbol)?)))
+    }
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Op::Eq | Op::Contains => ordering == Ordering::Equal,
+            Op::Ne => ordering != Ordering::Equal,
+            Op::Lt => ordering == Ordering::Less,
+            Op::Le => ordering != Ordering::Greater,
+            Op::Gt => ordering == Ordering::Greater,
+            Op::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// A comparison of a module field with a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub module: Module,
+    /// Field of the module; `None` compares a scalar module itself
+    pub field: Option<String>,
+    pub op: Op,
+    pub expected: String,
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    /// Parse `module[.field] <op> value`, e.g. `disk.percent <= 80`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("invalid rule '{s}' (e.g. 'memory.total_bytes >= 16G')");
+        let start = s.find(['<', '>', '=', '!', '~']).ok_or_else(invalid)?;
+        let (path, rest) = s.split_at(start);
+        let (op, expected) = Op::strip(rest).ok_or_else(invalid)?;
+        let (module, field) = match path.trim().split_once('.') {
+            Some((module, field)) => (module, Some(field.to_string())),
+            None => (path.trim(), None),
+        };
+        let expected = expected.trim().trim_matches(['"', '\'']).to_string();
+        if expected.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Rule {
+            module: module.parse()?,
+            field,
+            op,
+            expected,
+        })
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.module)?;
+        if let Some(field) = &self.field {
+            write!(f, ".{field}")?;
+        }
+        write!(f, " {} {}", self.op.symbol(), self.expected)
+    }
+}
+
+/// Rules of a check, from the command line or a file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    /// Smallest total memory, e.g. `16G`
+    pub min_memory: Option<String>,
+    /// Kernel version, optionally with an operator (default `>=`), e.g. `>=6.1`
+    pub kernel: Option<String>,
+    /// Distribution, e.g. `ubuntu`
+    pub distro: Option<String>,
+    /// Highest usage of every disk, e.g. `80%`
+    pub max_disk_usage: Option<String>,
+    /// Further rules such as `cpu.cores >= 8`
+    pub rules: Vec<String>,
+}
+
+impl Rules {
+    /// Read rules from a TOML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Rules> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| NeofetchError::file_read(path.display().to_string(), e))?;
+        toml::from_str(&content)
+            .map_err(|e| NeofetchError::config(format!("{}: {e}", path.display())))
+    }
+
+    /// Add `other`, whose shortcuts replace those set here
+    pub fn merge(&mut self, other: Rules) {
+        self.min_memory = other.min_memory.or(self.min_memory.take());
+        self.kernel = other.kernel.or(self.kernel.take());
+        self.distro = other.distro.or(self.distro.take());
+        self.max_disk_usage = other.max_disk_usage.or(self.max_disk_usage.take());
+        self.rules.extend(other.rules);
+    }
+
+    /// The shortcuts and rules as parsed [`Rule`]s
+    pub fn parse(&self) -> Result<Vec<Rule>> {
+        let shortcut = |module: Module, field: &str, op: Op, value: &Option<String>| {
+            value.as_ref().map(|value| {
+                let (op, value) = Op::strip(value.trim()).unwrap_or((op, value));
+                Rule {
+                    module,
+                    field: Some(field.to_string()).filter(|f| !f.is_empty()),
+                    op,
+                    expected: value.trim().to_string(),
+                }
+            })
+        };
+        let mut rules: Vec<Rule> = [
+            shortcut(Module::Memory, "total_bytes", Op::Ge, &self.min_memory),
+            shortcut(Module::Kernel, "", Op::Ge, &self.kernel),
+            shortcut(Module::Os, "distro", Op::Eq, &self.distro),
+            shortcut(Module::Disk, "percent", Op::Le, &self.max_disk_usage),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        for rule in &self.rules {
+            rules.push(rule.parse().map_err(NeofetchError::config)?);
+        }
+        if rules.is_empty() {
+            return Err(NeofetchError::config("no rules to check"));
+        }
+        Ok(rules)
+    }
+}
+
+/// Modules the rules need collected
+pub fn modules(rules: &[Rule]) -> Vec<Module> {
+    let mut modules = Vec::new();
+    for rule in rules {
+        if !modules.contains(&rule.module) {
+            modules.push(rule.module);
+        }
+    }
+    modules
+}
+
+/// Result of one rule
+#[derive(Debug, Clone, Serialize)]
+pub struct Outcome {
+    pub rule: String,
+    /// Value found, per item for list modules
+    pub actual: String,
+    pub passed: bool,
+}
+
+/// Leading version number of a text, e.g. `[6, 8, 0]` for `6.8.0-45-generic`
+fn version(s: &str) -> Option<Vec<u64>> {
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let parts: Vec<u64> = s[..end].split('.').map_while(|p| p.parse().ok()).collect();
+    (!parts.is_empty()).then_some(parts)
+}
+
+/// A size, percentage or plain number
+fn number(s: &str) -> std::result::Result<f64, String> {
+    match s.strip_suffix('%') {
+        Some(percent) => percent
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid percentage '{s}'")),
+        None => crate::units::parse_bytes(s),
+    }
+}
+
+fn compare(actual: &Value, op: Op, expected: &str) -> std::result::Result<bool, String> {
+    let text = match actual {
+        Value::String(s) => s.clone(),
+        Value::Null => return Err("no value".into()),
+        v => v.to_string(),
+    };
+    if op == Op::Contains {
+        return Ok(text.to_lowercase().contains(&expected.to_lowercase()));
+    }
+    if let Some(n) = actual.as_f64() {
+        let ordering = n.partial_cmp(&number(expected)?).ok_or("not a number")?;
+        return Ok(op.holds(ordering));
+    }
+    if let (Some(a), Some(mut e)) = (version(&text), version(expected)) {
+        // Missing parts count as 0, so 6.8.0 equals 6.8
+        e.resize(a.len().max(e.len()), 0);
+        let a = a.into_iter().chain(std::iter::repeat(0)).take(e.len());
+        return Ok(op.holds(a.cmp(e)));
+    }
+    match op {
+        Op::Eq => Ok(text.eq_ignore_ascii_case(expected)),
+        Op::Ne => Ok(!text.eq_ignore_ascii_case(expected)),
+        _ => Err(format!("cannot compare '{text}' with {}", op.symbol())),
+    }
+}
+
+/// Text of a value found, sizes in the configured units
+fn show(field: Option<&str>, value: &Value) -> String {
+    match (field, value) {
+        (Some(f), Value::Number(n)) if f.ends_with("_bytes") => {
+            crate::units::current().bytes(n.as_f64().unwrap_or_default())
+        }
+        (Some("percent"), Value::Number(n)) => format!("{:.0}%", n.as_f64().unwrap_or_default()),
+        (_, Value::String(s)) => s.clone(),
+        (_, v) => v.to_string(),
+    }
+}
+
+fn evaluate_rule(
+    rule: &Rule,
+    neofetch: &Neofetch,
+) -> std::result::Result<Vec<(String, bool)>, String> {
+    let (items, list) = match neofetch.value(rule.module).map_err(|e| e.to_string())? {
+        Value::Array(items) if items.is_empty() => return Err("none found".into()),
+        Value::Array(items) => (items, true),
+        value => (vec![value], false),
+    };
+    let mut results = Vec::new();
+    for item in items {
+        // Items of a list are told apart by their name
+        let name = ["name", "interface_name", "label"]
+            .iter()
+            .find_map(|k| item.get(k).and_then(Value::as_str).map(str::to_string))
+            .filter(|_| list && rule.field.as_deref() != Some("name"));
+        let actual = match &rule.field {
+            Some(field) => crate::template::context(rule.module, item, String::new())
+                .remove(field)
+                .ok_or_else(|| format!("{} has no field '{field}'", rule.module))?,
+            None if item.is_object() => {
+                return Err(format!(
+                    "{} needs a field, e.g. {}.name",
+                    rule.module, rule.module
+                ));
+            }
+            None => item,
+        };
+        let passed = compare(&actual, rule.op, &rule.expected)?;
+        let shown = show(rule.field.as_deref(), &actual);
+        let shown = match name {
+            Some(name) => format!("{name}: {shown}"),
+            None => shown,
+        };
+        results.push((shown, passed));
+    }
+    Ok(results)
+}
+
+/// Evaluate every rule against `neofetch`
+pub fn evaluate(rules: &[Rule], neofetch: &Neofetch) -> Vec<Outcome> {
+    rules
+        .iter()
+        .map(|rule| {
+            let (actual, passed) = match evaluate_rule(rule, neofetch) {
+                Ok(results) => {
+                    let actual: Vec<_> = results.iter().map(|(a, _)| a.as_str()).collect();
+                    (actual.join(", "), results.iter().all(|(_, p)| *p))
+                }
+                Err(e) => (format!("error: {e}"), false),
+            };
+            Outcome {
+                rule: rule.to_string(),
+                actual,
+                passed,
+            }
+        })
+        .collect()
+}
+
+/// Replace identifying data in the rules and values shown, after evaluation
+pub fn scrub(outcomes: &mut [Outcome], scrubber: &Scrubber) {
+    for outcome in outcomes {
+        outcome.rule = scrubber.text(&outcome.rule);
+        outcome.actual = scrubber.text(&outcome.actual);
+    }
+}
+
+/// Pass/fail table, or the outcomes as a structured document
+pub fn render(outcomes: &[Outcome], format: Format) -> Result<String> {
+    if format != Format::Text {
+        return serialize_list("checks", outcomes, format);
+    }
+    let width = |header: &str, cell: fn(&Outcome) -> &str| {
+        outcomes
+            .iter()
+            .map(|o| crate::utils::width(cell(o)))
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let rule_width = width("RULE", |o| &o.rule);
+    let actual_width = width("ACTUAL", |o| &o.actual);
+    let pad = |s: &str, w: usize| format!("{s}{}", " ".repeat(w - crate::utils::width(s)));
+
+    let mut lines = vec![format!(
+        "{GREEN}{BOLD}{}  {}  RESULT{RESET}",
+        pad("RULE", rule_width),
+        pad("ACTUAL", actual_width)
+    )];
+    for o in outcomes {
+        let result = match o.passed {
+            true => format!("{GREEN}pass{RESET}"),
+            false => format!("{RED}{BOLD}FAIL{RESET}"),
+        };
+        lines.push(format!(
+            "{}  {}  {result}",
+            pad(&o.rule, rule_width),
+            pad(&o.actual, actual_width)
+        ));
+    }
+    let passed = outcomes.iter().filter(|o| o.passed).count();
+    lines.push(format!("\n{passed} of {} checks passed", outcomes.len()));
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disk::Disk;
+    use crate::memory::Memory;
+
+    #[test]
+    fn test_parse_rules() {
+        let rule: Rule = "memory.total_bytes >= 16G".parse().unwrap();
+        assert_eq!(rule.module, Module::Memory);
+        assert_eq!(rule.op, Op::Ge);
+        assert_eq!(rule.to_string(), "memory.total_bytes >= 16G");
+        assert_eq!("kernel=6.1".parse::<Rule>().unwrap().op, Op::Eq);
+        assert!("nope.x > 1".parse::<Rule>().is_err());
+        assert!("memory.total_bytes".parse::<Rule>().is_err());
+
+        let rules = Rules {
+            kernel: Some("<6.0".into()),
+            max_disk_usage: Some("80%".into()),
+            ..Default::default()
+        };
+        let rules = rules.parse().unwrap();
+        assert_eq!(rules[0].to_string(), "kernel < 6.0");
+        assert_eq!(rules[1].to_string(), "disk.percent <= 80%");
+        assert!(Rules::default().parse().is_err());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut neofetch = Neofetch::empty();
+        neofetch.kernel = Ok("6.8.0-45-generic".into());
+        neofetch.memory = Ok(Memory {
+            total: 16_700_000_000,
+            used: 4_000_000_000,
+        });
+        neofetch.disk = Ok(vec![
+            Disk {
+                name: "/".into(),
+                total: 100,
+                used: 50,
+            },
+            Disk {
+                name: "/home".into(),
+                total: 100,
+                used: 91,
+            },
+        ]);
+        let rules = Rules {
+            min_memory: Some("16G".into()),
+            kernel: Some(">=6.1".into()),
+            distro: Some("ubuntu".into()),
+            max_disk_usage: Some("80%".into()),
+            rules: vec!["kernel ~ generic".into(), "kernel == 6.8".into()],
+        };
+        let outcomes = evaluate(&rules.parse().unwrap(), &neofetch);
+        let passed: Vec<_> = outcomes.iter().map(|o| o.passed).collect();
+        assert_eq!(passed, [true, true, false, false, true, true]);
+        assert!(outcomes[2].actual.starts_with("error: "));
+        assert_eq!(outcomes[3].actual, "/: 50%, /home: 91%");
+
+        let table = render(&outcomes, Format::Text).unwrap();
+        assert!(table.ends_with("4 of 6 checks passed"));
+        let toml = render(&outcomes, Format::Toml).unwrap();
+        assert!(toml.starts_with("[[checks]]\n"));
+    }
+
+    #[test]
+    fn test_scrub() {
+        let mut neofetch = Neofetch::empty();
+        neofetch.hostname = Ok("vm".into());
+        let rules = Rules {
+            rules: vec!["hostname == vm".into()],
+            ..Default::default()
+        };
+        let mut outcomes = evaluate(&rules.parse().unwrap(), &neofetch);
+        let redact = crate::redact::Redact {
+            enabled: true,
+            ..Default::default()
+        };
+        scrub(&mut outcomes, &redact.scrubber(&neofetch));
+        assert_eq!(outcomes[0].rule, "hostname == hostname");
+        assert_eq!(outcomes[0].actual, "hostname");
+        assert!(outcomes[0].passed);
+    }
+}
@@ -0,0 +1,299 @@
+//! Pluggable information lines
+//!
+//! Built-in modules are fixed fields of [`Neofetch`]. Other crates add lines by
+//! implementing [`Collector`] and calling [`register`] before collecting: the
+//! values are kept in [`Neofetch::extra`], show up in every output format under
+//! the collector's name and are addressed as [`Module::Extra`] everywhere a
+//! built-in module is (refresh, streaming, the cache, the HTTP server).
+//!
+//! ```no_run
+//! use futures::future::BoxFuture;
+//! use neofetch::collector::{Collector, register};
+//! use serde_json::{Value, json};
+//!
+//! struct Build;
+//!
+//! impl Collector for Build {
+//!     fn name(&self) -> &'static str {
+//!         "build"
+//!     }
+//!
+//!     fn collect(&self) -> BoxFuture<'_, neofetch::Result<Value>> {
+//!         Box::pin(async { Ok(json!("2024.06.1")) })
+//!     }
+//! }
+//!
+//! # async fn run() -> neofetch::Result<()> {
+//! register(Build)?;
+//! println!("{}", neofetch::Neofetch::new().await);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Module::Extra`]: crate::module::Module::Extra
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use serde::de::Deserializer;
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+
+/// Source of one additional information line
+pub trait Collector: Send + Sync {
+    /// Unique snake_case name, used as the JSON key and module name
+    fn name(&self) -> &'static str;
+
+    /// Label of the info line, by default the name in title case
+    fn label(&self) -> String {
+        title_case(self.name())
+    }
+
+    /// Whether the collector can run on this platform
+    fn supported(&self) -> bool {
+        true
+    }
+
+    /// Whether the value changes while the system runs, so watch mode refreshes it
+    fn volatile(&self) -> bool {
+        false
+    }
+
+    /// How long the value may be reused from the on-disk cache; `None` disables caching
+    fn ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Collect the structured value
+    fn collect(&self) -> BoxFuture<'_, Result<Value>>;
+
+    /// Text shown after the label; `None` hides the line
+    fn render(&self, value: &Value) -> Option<String> {
+        render_value(value)
+    }
+}
+
+static REGISTRY: RwLock<Vec<Arc<dyn Collector>>> = RwLock::new(Vec::new());
+
+/// Add a collector to every subsequent collection
+///
+/// Fails when the name is taken by a built-in module or another collector.
+pub fn register(collector: impl Collector + 'static) -> Result<()> {
+    let name = collector.name();
+    let mut registry = REGISTRY
+        .write()
+        .map_err(|e| NeofetchError::system_call(e.to_string()))?;
+    // Snapshot keys of built-in fields that differ from their module names
+    let renamed = ["uptime_seconds", "battery_percent"];
+    if Module::ALL.iter().any(|m| m.name() == name)
+        || renamed.contains(&name)
+        || registry.iter().any(|c| c.name() == name)
+    {
+        return Err(NeofetchError::config(format!(
+            "collector name '{name}' is already taken"
+        )));
+    }
+    registry.push(Arc::new(collector));
+    Ok(())
+}
+
+/// Remove the collector registered under `name`
+#[cfg(test)]
+pub(crate) fn unregister(name: &str) {
+    if let Ok(mut registry) = REGISTRY.write() {
+        registry.retain(|c| c.name() != name);
+    }
+}
+
+/// Registered collectors in registration order
+pub fn registered() -> Vec<Arc<dyn Collector>> {
+    REGISTRY.read().map(|r| r.clone()).unwrap_or_default()
+}
+
+/// Registered collector by name
+pub fn get(name: &str) -> Option<Arc<dyn Collector>> {
+    registered().into_iter().find(|c| c.name() == name)
+}
+
+/// Run the collector registered under `name`
+pub(crate) async fn collect(name: &str) -> Result<Value> {
+    let collector = get(name)
+        .ok_or_else(|| NeofetchError::data_unavailable(format!("no collector named '{name}'")))?;
+    if !collector.supported() {
+        return Err(NeofetchError::UnsupportedPlatform);
+    }
+    collector.collect().await
+}
+
+pub(crate) fn title_case(name: &str) -> String {
+    name.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Default rendering: scalars as-is, lists joined with commas, objects as `key: value`
+pub fn render_value(value: &Value) -> Option<String> {
+    let s = match value {
+        Value::Null => return None,
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(render_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(fields) => fields
+            .iter()
+            .filter_map(|(k, v)| Some(format!("{k}: {}", render_value(v)?)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        v => v.to_string(),
+    };
+    (!s.is_empty()).then_some(s)
+}
+
+/// Label and text of a collected line, using the registered collector when there is one
+///
+/// Snapshots loaded without the collector fall back to the default label and renderer.
+pub(crate) fn line(name: &str, value: &Value) -> Option<(String, String)> {
+    match get(name) {
+        Some(c) => Some((c.label(), c.render(value)?)),
+        None => Some((title_case(name), render_value(value)?)),
+    }
+}
+
+/// Values of registered collectors, keyed by name in registration order
+#[derive(Debug, Clone, Default)]
+pub struct Extra(Vec<(String, Result<Value>)>);
+
+impl Extra {
+    /// Every registered collector marked as not collected yet
+    pub(crate) fn pending() -> Extra {
+        Extra(
+            registered()
+                .iter()
+                .map(|c| {
+                    let pending = Err(NeofetchError::data_unavailable("not collected"));
+                    (c.name().to_string(), pending)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Result<Value>> {
+        self.0.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    pub fn set(&mut self, name: &str, value: Result<Value>) {
+        match self.0.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value,
+            None => self.0.push((name.to_string(), value)),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Result<Value>)> {
+        self.0.iter().map(|(n, v)| (n.as_str(), v))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Serialize for Extra {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in &self.0 {
+            match value {
+                Ok(v) => map.serialize_entry(name, v)?,
+                Err(e) => map.serialize_entry(name, &json!({ "error": e }))?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Extra {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let map = Map::<String, Value>::deserialize(deserializer)?;
+        Ok(Extra(
+            map.into_iter()
+                .map(|(name, value)| {
+                    let error = value
+                        .as_object()
+                        .filter(|o| o.len() == 1)
+                        .and_then(|o| o.get("error"))
+                        .and_then(|e| serde_json::from_value(e.clone()).ok());
+                    (name, error.map_or(Ok(value), Err))
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Answer;
+
+    impl Collector for Answer {
+        fn name(&self) -> &'static str {
+            "the_answer"
+        }
+
+        fn collect(&self) -> BoxFuture<'_, Result<Value>> {
+            Box::pin(async { Ok(json!({ "value": 42, "unit": "units" })) })
+        }
+    }
+
+    /// Unregisters on drop, so the collector does not stay listed among the
+    /// modules of later tests, even when an assertion fails
+    struct Registered(&'static str);
+
+    impl Drop for Registered {
+        fn drop(&mut self) {
+            unregister(self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_collector() {
+        register(Answer).unwrap();
+        let registered = Registered("the_answer");
+        assert!(register(Answer).is_err());
+
+        let module: Module = "the-answer".parse().unwrap();
+        assert_eq!(module, Module::Extra("the_answer"));
+
+        let mut neofetch = crate::Neofetch::empty();
+        neofetch.refresh(&[module]).await;
+        assert_eq!(neofetch.value(module).unwrap()["value"], 42);
+        assert!(
+            neofetch
+                .info()
+                .contains("The Answer: \u{1b}[0mvalue: 42, unit: units")
+        );
+
+        let json = serde_json::to_value(&neofetch).unwrap();
+        assert_eq!(json["the_answer"]["value"], 42);
+        let loaded: crate::Neofetch = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.value(module).unwrap()["unit"], "units");
+
+        drop(registered);
+        assert!(get("the_answer").is_none());
+        assert!(!Module::all().contains(&module));
+    }
+}
@@ -18,6 +18,8 @@ pub const BRIGHT_WHITE: &str = "\x1b[97m";
 
 pub const RESET: &str = "\x1b[0m";
 pub const BOLD: &str = "\x1b[1m";
+pub const DIM: &str = "\x1b[2m";
+pub const REVERSE: &str = "\x1b[7m";
 
 pub const BLACK_BG: &str = "\x1b[40m";
 pub const RED_BG: &str = "\x1b[41m";
@@ -68,3 +70,11 @@ pub fn cursor_forward(n: usize) -> String {
 pub fn cursor_backward(n: usize) -> String {
     format!("\x1B[{n}D")
 }
+
+pub const CURSOR_HOME: &str = "\x1b[H";
+pub const CLEAR_LINE: &str = "\x1b[K";
+pub const CLEAR_BELOW: &str = "\x1b[J";
+pub const HIDE_CURSOR: &str = "\x1b[?25l";
+pub const SHOW_CURSOR: &str = "\x1b[?25h";
+pub const ALT_SCREEN: &str = "\x1b[?1049h";
+pub const MAIN_SCREEN: &str = "\x1b[?1049l";
@@ -0,0 +1,429 @@
+//! User configuration
+//!
+//! Read from `$XDG_CONFIG_HOME/neofetch/config.toml` (or `--config FILE`) once at
+//! startup and validated before anything is collected, so mistakes are reported
+//! immediately instead of showing up as missing lines.
+//!
+//! ```toml
+//! # Info lines in display order; unlisted modules are hidden
+//! modules = ["os", "host", "kernel", "build", "uptime", "cpu", "memory", "vpn"]
+//!
+//! # Labels in German instead of the language of the locale
+//! language = "de"
+//!
+//! # Colors for a light or dark background instead of asking the terminal
+//! theme = "light"
+//!
+//! [[custom]]
+//! name = "build"
+//! file = "/etc/build-id"
+//! ttl = "1d"
+//!
+//! [[custom]]
+//! name = "vpn"
+//! label = "VPN"
+//! command = "wg show wg0 endpoints"
+//! regex = '\s(\S+):\d+$'
+//! timeout = "500ms"
+//!
+//! # Text of a module from its fields, see [`crate::template`]
+//! [format]
+//! memory = "{used_gib:.1}/{total_gib:.0} GiB"
+//!
+//! # Displayed units, see [`crate::units`]
+//! [units]
+//! bytes = "iec"
+//! temperature = "fahrenheit"
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::utils::width;
+use serde::{Deserialize, Deserializer};
+
+use crate::color::{BOLD, RED, RESET};
+use crate::custom::CustomModule;
+use crate::error::{NeofetchError, Result};
+use crate::glyph::IconSet;
+use crate::history::History;
+use crate::i18n::Language;
+use crate::module::Module;
+use crate::palette::Palette;
+use crate::redact::Redact;
+use crate::script::ScriptModule;
+use crate::template::Template;
+use crate::theme::Mode;
+use crate::units::Units;
+
+/// Info lines shown when the configuration does not list any
+pub const DEFAULT_LAYOUT: [Module; 22] = [
+    Module::Os,
+    Module::Host,
+    Module::Rom,
+    Module::Baseband,
+    Module::Kernel,
+    Module::Uptime,
+    Module::Packages,
+    Module::Shell,
+    Module::Display,
+    Module::De,
+    Module::Wm,
+    Module::Terminal,
+    Module::Disk,
+    Module::Cpu,
+    Module::Gpu,
+    Module::Memory,
+    Module::Temperature,
+    Module::Battery,
+    Module::LocalIp,
+    Module::Ip,
+    Module::Network,
+    Module::Locale,
+];
+
+/// Parse a duration such as `2`, `1.5s`, `500ms`, `10m`, `12h` or `7d`
+pub fn parse_duration(s: &str) -> std::result::Result<Duration, String> {
+    let s = s.trim();
+    let units = [
+        ("ms", 0.001),
+        ("s", 1.0),
+        ("m", 60.0),
+        ("h", 3600.0),
+        ("d", 86400.0),
+    ];
+    let (number, scale) = units
+        .iter()
+        .find_map(|(suffix, scale)| Some((s.strip_suffix(suffix)?, *scale)))
+        .unwrap_or((s, 1.0));
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|n| n * scale)
+        .filter(|n| n.is_finite() && *n > 0.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| format!("invalid duration '{s}' (e.g. 2, 1.5s, 500ms, 10m, 12h, 7d)"))
+}
+
+/// Deserialize a duration from seconds or a string accepted by [`parse_duration`]
+pub(crate) fn duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Duration>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Seconds(u64),
+        Text(String),
+    }
+
+    match Raw::deserialize(deserializer)? {
+        Raw::Seconds(0) => Err(serde::de::Error::custom("duration must be greater than 0")),
+        Raw::Seconds(s) => Ok(Some(Duration::from_secs(s))),
+        Raw::Text(s) => parse_duration(&s)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Settings read from the configuration file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Info lines in display order, by module name; empty keeps [`DEFAULT_LAYOUT`]
+    pub modules: Vec<String>,
+    /// Command and file modules
+    pub custom: Vec<CustomModule>,
+    /// Rhai scripts
+    pub script: Vec<ScriptModule>,
+    /// Format template per module name
+    pub format: BTreeMap<String, String>,
+    /// Displayed units of sizes, temperatures and clock speeds
+    pub units: Units,
+    /// Language of the labels; by default the one of the detected locale
+    pub language: Option<Language>,
+    /// Title line and layout of the info block
+    pub info: Info,
+    /// Color blocks below the info lines
+    pub palette: Palette,
+    /// Terminal background the colors are adapted to
+    pub theme: Mode,
+    /// Masking of identifying data
+    pub redact: Redact,
+    /// Recording of runs for the trend views
+    pub history: History,
+    /// Parsed [`Config::format`], keyed by module name
+    #[serde(skip)]
+    templates: HashMap<String, Template>,
+    /// Directory of the configuration file, for relative script paths
+    #[serde(skip)]
+    pub dir: Option<PathBuf>,
+}
+
+/// Title line and layout of the info block
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Info {
+    /// Start every value in the same column
+    pub align: bool,
+    /// Text between a label and its value
+    pub separator: String,
+    /// `user@host`, `hostname`, `none`, or a text with `{user}` and `{hostname}`
+    pub title: String,
+    /// Repeated under the title to its width; empty for no underline
+    pub underline: String,
+    /// Icons shown before the labels
+    pub icons: IconSet,
+    /// Icon per module name, replacing the one of the set
+    pub icon: BTreeMap<String, String>,
+    /// Show the labels; without them only the icons are shown
+    pub labels: bool,
+}
+
+impl Default for Info {
+    fn default() -> Self {
+        Info {
+            align: false,
+            separator: ": ".into(),
+            title: "user@host".into(),
+            underline: "-".into(),
+            icons: IconSet::None,
+            icon: BTreeMap::new(),
+            labels: true,
+        }
+    }
+}
+
+impl Info {
+    /// Title with highlighted user and host names, empty when disabled
+    pub fn title(&self, user: &str, hostname: &str) -> String {
+        let format = match self.title.as_str() {
+            "user@host" => "{user}@{hostname}",
+            "hostname" => "{hostname}",
+            "none" => "",
+            custom => custom,
+        };
+        format
+            .replace("{user}", &format!("{RED}{BOLD}{user}{RESET}"))
+            .replace("{hostname}", &format!("{RED}{BOLD}{hostname}{RESET}"))
+    }
+
+    /// Label of a line of `module` with its icon; continuation lines stay empty
+    pub fn label(&self, module: Module, label: String) -> String {
+        let icon = match self.icon.get(module.name()) {
+            Some(icon) => Some(icon.as_str()),
+            None => self.icons.icon(module),
+        };
+        match icon {
+            _ if label.is_empty() => label,
+            Some(icon) if !self.labels => icon.to_string(),
+            Some(icon) => format!("{icon} {label}"),
+            None => label,
+        }
+    }
+
+    /// Underline of a title `columns` wide
+    pub fn underline(&self, columns: usize) -> String {
+        match width(&self.underline) {
+            0 => String::new(),
+            w => self.underline.repeat(columns.div_ceil(w)),
+        }
+    }
+}
+
+static CURRENT: RwLock<Option<Arc<Config>>> = RwLock::new(None);
+
+/// Configuration applied with [`Config::apply`], or the defaults
+pub fn current() -> Arc<Config> {
+    CURRENT
+        .read()
+        .ok()
+        .and_then(|c| c.clone())
+        .unwrap_or_default()
+}
+
+impl Config {
+    /// Default location of the configuration file
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("neofetch").join("config.toml"))
+    }
+
+    /// Parse and validate a configuration
+    pub fn parse(content: &str) -> Result<Config> {
+        let mut config: Config =
+            toml::from_str(content).map_err(|e| NeofetchError::config(e.to_string()))?;
+        config.validate()?;
+        config.templates = config.templates()?;
+        Ok(config)
+    }
+
+    /// Load `path`, or the default location when it exists
+    pub fn load(path: Option<&Path>) -> Result<Config> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Config::path().filter(|p| p.exists()) {
+                Some(path) => path,
+                None => return Ok(Config::default()),
+            },
+        };
+        let content = crate::utils::read_file_to_string_sync(&path)?;
+        let mut config = Config::parse(&content).map_err(|e| match e {
+            NeofetchError::Config(e) => NeofetchError::config(format!("{}: {e}", path.display())),
+            e => e,
+        })?;
+        config.dir = path.parent().map(Path::to_path_buf);
+        Ok(config)
+    }
+
+    /// Names of the custom and script modules
+    fn names(&self) -> impl Iterator<Item = &str> {
+        let custom = self.custom.iter().map(|c| c.name.as_str());
+        custom.chain(self.script.iter().map(|s| s.name.as_str()))
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.palette.validate()?;
+        for custom in &self.custom {
+            custom.validate()?;
+        }
+        let names: Vec<_> = self.names().collect();
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(NeofetchError::config(format!(
+                    "module '{name}' is defined twice"
+                )));
+            }
+        }
+        for name in self.modules.iter().chain(self.info.icon.keys()) {
+            self.module(name)?;
+        }
+        Ok(())
+    }
+
+    /// Canonical name of a built-in, registered, custom or script module
+    fn module(&self, name: &str) -> Result<String> {
+        if let Ok(module) = name.parse::<Module>() {
+            return Ok(module.name().to_string());
+        }
+        let name = name.replace('-', "_");
+        match self.names().any(|n| n == name) {
+            true => Ok(name),
+            false => Err(NeofetchError::config(format!("unknown module '{name}'"))),
+        }
+    }
+
+    /// Parse the format templates, checking their fields against the module
+    fn templates(&self) -> Result<HashMap<String, Template>> {
+        let mut templates = HashMap::new();
+        for (name, format) in &self.format {
+            let error = |e: String| NeofetchError::config(format!("format.{name}: {e}"));
+            let module = self.module(name)?;
+            let template: Template = format.parse().map_err(error)?;
+            if let Ok(module) = module.parse::<Module>() {
+                crate::template::validate(module, &template).map_err(error)?;
+            }
+            templates.insert(module, template);
+        }
+        Ok(templates)
+    }
+
+    /// Format template of a module
+    pub fn template(&self, module: Module) -> Option<&Template> {
+        self.templates.get(module.name())
+    }
+
+    /// Register the custom and script modules, set the units and language and
+    /// make this the [`current`] configuration
+    pub async fn apply(self) -> Result<()> {
+        crate::units::set(self.units);
+        let language = match self.language {
+            Some(language) => language,
+            None => crate::locale::get_locale()
+                .await
+                .map(|locale| Language::from_locale(&locale))
+                .unwrap_or_default(),
+        };
+        crate::i18n::set(language);
+        for custom in &self.custom {
+            crate::collector::register(custom.collector()?)?;
+        }
+        crate::script::load(&self.script, self.dir.as_deref())?;
+        let mut current = CURRENT
+            .write()
+            .map_err(|e| NeofetchError::system_call(e.to_string()))?;
+        *current = Some(Arc::new(self));
+        Ok(())
+    }
+
+    /// Modules of the info lines in display order
+    pub fn layout(&self) -> Vec<Module> {
+        if self.modules.is_empty() {
+            let extra = crate::collector::registered();
+            return DEFAULT_LAYOUT
+                .into_iter()
+                .chain(extra.iter().map(|c| Module::Extra(c.name())))
+                .collect();
+        }
+        self.modules.iter().filter_map(|m| m.parse().ok()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1m"), Ok(Duration::from_secs(60)));
+        assert_eq!(parse_duration("7d"), Ok(Duration::from_secs(7 * 86400)));
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("fast").is_err());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let config = Config::parse(
+            r#"
+            modules = ["os", "wm-theme", "build"]
+
+            [format]
+            build = "v{value}"
+
+            [[custom]]
+            name = "build"
+            file = "/etc/build-id"
+            ttl = 3600
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.custom[0].ttl, Some(Duration::from_secs(3600)));
+
+        assert!(config.template(Module::Extra("build")).is_some());
+
+        assert!(Config::parse(r#"modules = ["nope"]"#).is_err());
+        assert!(Config::parse("colour = true").is_err());
+        let both = r#"
+            [[custom]]
+            name = "x"
+            file = "/a"
+            command = "b"
+        "#;
+        assert!(Config::parse(both).is_err());
+    }
+
+    #[test]
+    fn test_info_config() {
+        let info = Config::parse("info = { title = '{user} on {hostname}', underline = '=' }");
+        let info = info.unwrap().info;
+        assert_eq!(
+            info.title("me", "box"),
+            format!("{RED}{BOLD}me{RESET} on {RED}{BOLD}box{RESET}")
+        );
+        assert_eq!(info.underline(9), "=========");
+    }
+}
@@ -4,17 +4,25 @@
 //! across different platforms.
 
 use crate::error::{NeofetchError, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// CPU information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cpu {
     /// CPU model name
     pub name: String,
     /// Number of CPU cores
     pub cores: u32,
+    /// Number of hardware threads (logical processors)
+    #[serde(default)]
+    pub threads: u32,
     /// CPU speed in MHz
+    #[serde(rename = "speed_mhz")]
     pub speed: u32,
+    /// Maximum CPU speed in MHz
+    #[serde(default, rename = "max_speed_mhz")]
+    pub max_speed: u32,
 }
 
 impl Display for Cpu {
@@ -25,7 +33,7 @@ impl Display for Cpu {
             parts.push(format!("({})", self.cores));
         }
         if self.speed > 0 {
-            parts.push(format!("{:.2} GHz", self.speed as f64 / 1000.));
+            parts.push(crate::units::current().frequency(self.speed as f64));
         }
 
         write!(f, "{}", parts.join(" "))
@@ -45,9 +53,11 @@ pub async fn get_cpu() -> Result<Cpu> {
         #[serde(rename = "Name")]
         name: String,
         #[serde(rename = "NumberOfLogicalProcessors")]
-        number_of_cores: u32,
+        number_of_logical_processors: u32,
         #[serde(rename = "CurrentClockSpeed")]
         current_clock_speed: u32,
+        #[serde(rename = "MaxClockSpeed")]
+        max_clock_speed: u32,
     }
 
     // Use WMI to query processor information
@@ -59,8 +69,10 @@ pub async fn get_cpu() -> Result<Cpu> {
         .first()
         .map(|proc| Cpu {
             name: proc.name.trim().to_string(),
-            cores: proc.number_of_cores,
+            cores: proc.number_of_logical_processors,
+            threads: proc.number_of_logical_processors,
             speed: proc.current_clock_speed,
+            max_speed: proc.max_clock_speed,
         })
         .ok_or_else(|| NeofetchError::data_unavailable("No CPU information found"))
 }
@@ -83,7 +95,9 @@ pub async fn get_cpu() -> Result<Cpu> {
     let mut cpu = Cpu {
         name,
         cores: 0,
+        threads: 0,
         speed: 0,
+        max_speed: 0,
     };
 
     // Parse CPU frequency (MHz)
@@ -100,6 +114,19 @@ pub async fn get_cpu() -> Result<Cpu> {
         cpu.cores = cores;
     }
 
+    // Logical processors are listed one block each
+    cpu.threads = content
+        .lines()
+        .filter(|line| line.starts_with("processor"))
+        .count() as u32;
+
+    if let Ok(khz) =
+        read_file_to_string("/sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq").await
+        && let Ok(khz) = khz.trim().parse::<u32>()
+    {
+        cpu.max_speed = khz / 1000;
+    }
+
     Ok(cpu)
 }
 
@@ -111,11 +138,16 @@ pub async fn get_cpu() -> Result<Cpu> {
     let name = macos::get_cpu_brand().await?;
     let cores = macos::get_cpu_core_count().await?;
     let speed_mhz = macos::get_cpu_frequency().await? as u32;
+    // Unknown values stay 0, so templates leave them out
+    let threads = macos::get_cpu_thread_count().await.unwrap_or(0);
+    let max_speed = macos::get_cpu_max_frequency().await.unwrap_or(0) as u32;
 
     Ok(Cpu {
         name: name.trim().to_string(),
         cores,
+        threads,
         speed: speed_mhz,
+        max_speed,
     })
 }
 
@@ -133,7 +165,9 @@ pub async fn get_cpu() -> Result<Cpu> {
     let mut cpu = Cpu {
         name,
         cores: 0,
+        threads: 0,
         speed: 0,
+        max_speed: 0,
     };
 
     // Get core count from /sys/devices/system/cpu/present
@@ -141,10 +175,18 @@ pub async fn get_cpu() -> Result<Cpu> {
         if let Some((left, right)) = content.trim().split_once('-') {
             if let (Ok(start), Ok(end)) = (left.parse::<u32>(), right.parse::<u32>()) {
                 cpu.cores = end - start + 1;
+                cpu.threads = cpu.cores;
             }
         }
     }
 
+    if let Ok(khz) =
+        read_file_to_string("/sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq").await
+        && let Ok(khz) = khz.trim().parse::<u32>()
+    {
+        cpu.max_speed = khz / 1000;
+    }
+
     // Calculate average CPU frequency
     if cpu.cores > 0 {
         let mut total_freq = 0u64;
@@ -0,0 +1,200 @@
+//! Command and file modules defined in the configuration
+//!
+//! Each `[[custom]]` entry becomes a registered [`Collector`], so its line is
+//! placed, cached and serialized like any other module.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::collector::Collector;
+use crate::error::{NeofetchError, Result};
+use crate::utils::{execute_command, read_file_to_string};
+
+/// How long a command may run when the entry sets no timeout
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A `[[custom]]` entry of the configuration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomModule {
+    /// Module name, used in `modules` and as the JSON key
+    pub name: String,
+    /// Label of the line, by default the name in title case
+    pub label: Option<String>,
+    /// Shell command whose output is shown
+    pub command: Option<String>,
+    /// File whose content is shown
+    pub file: Option<PathBuf>,
+    /// Extract the first capture group (or the whole match) from the output
+    pub regex: Option<String>,
+    /// Maximum run time of the command
+    #[serde(default, deserialize_with = "crate::config::duration")]
+    pub timeout: Option<Duration>,
+    /// How long the value may be reused from the on-disk cache
+    #[serde(default, deserialize_with = "crate::config::duration")]
+    pub ttl: Option<Duration>,
+    /// Refresh the value in watch mode
+    #[serde(default)]
+    pub volatile: bool,
+}
+
+impl CustomModule {
+    pub(crate) fn validate(&self) -> Result<()> {
+        let error = |reason: &str| {
+            Err(NeofetchError::config(format!(
+                "custom module '{}': {reason}",
+                self.name
+            )))
+        };
+        let valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid_name {
+            return error("name must be snake_case");
+        }
+        if self.command.is_some() == self.file.is_some() {
+            return error("set exactly one of `command` or `file`");
+        }
+        if let Some(re) = &self.regex
+            && let Err(e) = Regex::new(re)
+        {
+            return error(&e.to_string());
+        }
+        Ok(())
+    }
+
+    /// Collector for this entry
+    pub(crate) fn collector(&self) -> Result<Custom> {
+        let source = match (&self.command, &self.file) {
+            (Some(command), _) => Source::Command(command.clone()),
+            (_, Some(file)) => Source::File(file.clone()),
+            _ => return Err(NeofetchError::config("custom module without a source")),
+        };
+        let regex = self
+            .regex
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| NeofetchError::config(e.to_string()))?;
+        Ok(Custom {
+            // Registered collectors live for the whole run
+            name: Box::leak(self.name.clone().into_boxed_str()),
+            label: self.label.clone(),
+            source,
+            regex,
+            timeout: self.timeout.unwrap_or(DEFAULT_TIMEOUT),
+            ttl: self.ttl,
+            volatile: self.volatile,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Command(String),
+    File(PathBuf),
+}
+
+/// Collector of a [`CustomModule`]
+#[derive(Debug)]
+pub struct Custom {
+    name: &'static str,
+    label: Option<String>,
+    source: Source,
+    regex: Option<Regex>,
+    timeout: Duration,
+    ttl: Option<Duration>,
+    volatile: bool,
+}
+
+/// Run a command line through the platform shell
+pub(crate) async fn shell(command: &str, timeout: Duration) -> Result<String> {
+    #[cfg(windows)]
+    let (sh, args) = ("cmd", ["/C", command]);
+    #[cfg(not(windows))]
+    let (sh, args) = ("sh", ["-c", command]);
+    tokio::time::timeout(timeout, execute_command(sh, &args))
+        .await
+        .map_err(|_| NeofetchError::timeout(format!("'{command}' after {timeout:?}")))?
+}
+
+/// First capture group of `regex` in `text`, or the whole match
+fn extract(regex: &Regex, text: &str) -> Option<String> {
+    let captures = regex.captures(text)?;
+    let m = captures.get(1).or_else(|| captures.get(0))?;
+    Some(m.as_str().trim().to_string())
+}
+
+impl Custom {
+    async fn value(&self) -> Result<Value> {
+        let output = match &self.source {
+            Source::Command(command) => shell(command, self.timeout).await?,
+            Source::File(path) => read_file_to_string(path).await?,
+        };
+        let text = match &self.regex {
+            Some(regex) => extract(regex, &output).ok_or_else(|| {
+                NeofetchError::data_unavailable(format!("no match for '{}'", regex.as_str()))
+            })?,
+            None => output.trim().to_string(),
+        };
+        Ok(Value::String(text))
+    }
+}
+
+impl Collector for Custom {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn label(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => crate::collector::title_case(self.name),
+        }
+    }
+
+    fn volatile(&self) -> bool {
+        self.volatile
+    }
+
+    fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    fn collect(&self) -> BoxFuture<'_, Result<Value>> {
+        Box::pin(self.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let re = Regex::new(r"VERSION_ID=(\S+)").unwrap();
+        assert_eq!(
+            extract(&re, "NAME=x\nVERSION_ID=24.04\n"),
+            Some("24.04".into())
+        );
+        let re = Regex::new(r"\d+\.\d+").unwrap();
+        assert_eq!(extract(&re, "version 6.8.0"), Some("6.8".into()));
+        assert_eq!(extract(&re, "none"), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_timeout() {
+        let fast = shell("echo hi", DEFAULT_TIMEOUT).await;
+        assert_eq!(fast.unwrap(), "hi");
+        let slow = shell("sleep 5", Duration::from_millis(50)).await;
+        assert!(matches!(slow, Err(NeofetchError::Timeout(_))));
+    }
+}
@@ -0,0 +1,421 @@
+//! Snapshot comparison
+//!
+//! Compares two [`Neofetch`] snapshots field by field through their serialized
+//! form. List entries (disks, GPUs, interfaces, sensors) are matched by name
+//! rather than position, so a reordered list does not show up as a change.
+
+use std::fmt::Display;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::Neofetch;
+use crate::color::{BOLD, GREEN, RED, RESET, YELLOW};
+use crate::error::{NeofetchError, Result};
+use crate::output::{Format, serialize_list};
+use crate::uptime::Time;
+
+/// Fields used to identify an entry inside a list
+const ENTRY_KEYS: [&str; 3] = ["name", "interface_name", "label"];
+
+/// One step of a path into a snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Object field
+    Key(String),
+    /// List entry, identified by name (or index when it has none)
+    Entry(String),
+}
+
+/// Location of a changed value, e.g. `disk(/).used_bytes`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Path(pub Vec<Segment>);
+
+impl Path {
+    fn join(&self, segment: Segment) -> Path {
+        let mut v = self.0.clone();
+        v.push(segment);
+        Path(v)
+    }
+
+    /// Name of the last object field, which carries the unit suffix
+    fn field(&self) -> &str {
+        self.0
+            .iter()
+            .rev()
+            .find_map(|s| match s {
+                Segment::Key(k) => Some(k.as_str()),
+                Segment::Entry(_) => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// JSON Pointer style path (`/disk/~1/used_bytes`)
+    pub fn to_pointer(&self) -> String {
+        self.0
+            .iter()
+            .map(|s| match s {
+                Segment::Key(k) | Segment::Entry(k) => {
+                    format!("/{}", k.replace('~', "~0").replace('/', "~1"))
+                }
+            })
+            .collect()
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Key(k) if i == 0 => write!(f, "{k}")?,
+                Segment::Key(k) => write!(f, ".{k}")?,
+                Segment::Entry(e) => write!(f, "({e})")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Kind of change at a path
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Present only in the new snapshot
+    Added(Value),
+    /// Present only in the old snapshot
+    Removed(Value),
+    /// Present in both with different values
+    Changed { old: Value, new: Value },
+}
+
+/// A single difference between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub path: Path,
+    pub change: Change,
+}
+
+impl Entry {
+    /// Numeric difference (new - old) when both values are numbers
+    pub fn delta(&self) -> Option<f64> {
+        match &self.change {
+            Change::Changed { old, new } => Some(new.as_f64()? - old.as_f64()?),
+            _ => None,
+        }
+    }
+}
+
+/// JSON patch-like operation
+#[derive(Debug, Serialize)]
+struct Operation {
+    op: &'static str,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delta: Option<f64>,
+}
+
+/// Compare two snapshots
+pub fn diff(old: &Neofetch, new: &Neofetch) -> Result<Vec<Entry>> {
+    let old = serde_json::to_value(old).map_err(|e| NeofetchError::serialization(e.to_string()))?;
+    let new = serde_json::to_value(new).map_err(|e| NeofetchError::serialization(e.to_string()))?;
+    Ok(diff_values(&old, &new))
+}
+
+/// Compare two serialized snapshots
+pub fn diff_values(old: &Value, new: &Value) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    compare(&Path::default(), old, new, &mut entries);
+    entries
+}
+
+fn compare(path: &Path, old: &Value, new: &Value, out: &mut Vec<Entry>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => compare_maps(path, a, b, out),
+        (Value::Array(a), Value::Array(b)) if is_keyed(a) && is_keyed(b) => {
+            compare_maps(path, &keyed(a), &keyed(b), out)
+        }
+        _ if old != new => out.push(Entry {
+            path: path.clone(),
+            change: Change::Changed {
+                old: old.clone(),
+                new: new.clone(),
+            },
+        }),
+        _ => {}
+    }
+}
+
+fn compare_maps(
+    path: &Path,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    out: &mut Vec<Entry>,
+) {
+    // Entries of a keyed list are tagged with a leading NUL by `keyed`
+    let segment = |k: &str| match k.strip_prefix('\0') {
+        Some(name) => Segment::Entry(name.to_string()),
+        None => Segment::Key(k.to_string()),
+    };
+
+    for (k, a) in old {
+        let p = path.join(segment(k));
+        match new.get(k) {
+            Some(b) => compare(&p, a, b, out),
+            None => out.push(Entry {
+                path: p,
+                change: Change::Removed(a.clone()),
+            }),
+        }
+    }
+    for (k, b) in new {
+        if !old.contains_key(k) {
+            out.push(Entry {
+                path: path.join(segment(k)),
+                change: Change::Added(b.clone()),
+            });
+        }
+    }
+}
+
+/// A list can be matched by name when it is empty or holds objects
+fn is_keyed(list: &[Value]) -> bool {
+    list.iter().all(Value::is_object)
+}
+
+fn keyed(list: &[Value]) -> Map<String, Value> {
+    let mut map = Map::new();
+    for (i, item) in list.iter().enumerate() {
+        let name = ENTRY_KEYS
+            .iter()
+            .find_map(|k| item.get(k).and_then(Value::as_str))
+            .map(str::to_string)
+            .unwrap_or_else(|| i.to_string());
+
+        let mut key = format!("\0{name}");
+        let mut n = 2;
+        while map.contains_key(&key) {
+            key = format!("\0{name}#{n}");
+            n += 1;
+        }
+        map.insert(key, item.clone());
+    }
+    map
+}
+
+/// Format a value using the unit carried by its field name
+fn format_value(field: &str, value: &Value) -> String {
+    let units = crate::units::current();
+    match (value.as_f64(), value) {
+        (Some(n), _) if field.ends_with("_bytes") => units.bytes(n),
+        (Some(n), _) if field.ends_with("_seconds") => Time(n as u64).to_string(),
+        (Some(n), _) if field.ends_with("_celsius") => units.temperature(n),
+        (Some(n), _) if field.ends_with("_mhz") => units.frequency(n),
+        (_, Value::String(s)) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+fn format_delta(field: &str, delta: f64) -> String {
+    let sign = if delta < 0.0 { "-" } else { "+" };
+    let abs = delta.abs();
+    let units = crate::units::current();
+    if field.ends_with("_bytes") {
+        format!("{sign}{}", units.bytes(abs))
+    } else if field.ends_with("_seconds") {
+        format!("{sign}{}", Time(abs as u64))
+    } else if field.ends_with("_celsius") {
+        format!("{sign}{}", units.temperature_difference(abs))
+    } else if field.ends_with("_mhz") {
+        format!("{sign}{}", units.frequency(abs))
+    } else {
+        format!("{sign}{abs}")
+    }
+}
+
+/// Render the differences as colored text
+pub fn render_text(entries: &[Entry]) -> String {
+    if entries.is_empty() {
+        return "No changes".to_string();
+    }
+
+    let mut lines = Vec::new();
+    for entry in entries {
+        let field = entry.path.field();
+        let path = &entry.path;
+        let line = match &entry.change {
+            Change::Added(v) => format!("{GREEN}{BOLD}+ {path}: {RESET}{}", format_value(field, v)),
+            Change::Removed(v) => format!("{RED}{BOLD}- {path}: {RESET}{}", format_value(field, v)),
+            Change::Changed { old, new } => {
+                let mut line = format!(
+                    "{YELLOW}{BOLD}~ {path}: {RESET}{} -> {}",
+                    format_value(field, old),
+                    format_value(field, new)
+                );
+                if let Some(delta) = entry.delta() {
+                    line.push_str(&format!(" ({})", format_delta(field, delta)));
+                }
+                line
+            }
+        };
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Copy of `value` without its nulls, which TOML cannot represent
+fn without_nulls(value: &Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::Array(items) => Some(Value::Array(
+            items.iter().filter_map(without_nulls).collect(),
+        )),
+        Value::Object(map) => Some(Value::Object(
+            map.iter()
+                .filter_map(|(k, v)| Some((k.clone(), without_nulls(v)?)))
+                .collect(),
+        )),
+        v => Some(v.clone()),
+    }
+}
+
+/// Render the differences as colored text, or as a JSON patch-like list of
+/// operations in the structured formats. In TOML, null values (an unset
+/// field) are left out.
+pub fn render(entries: &[Entry], format: Format) -> Result<String> {
+    if format == Format::Text {
+        return Ok(render_text(entries));
+    }
+    let keep = |v: &Value| match format {
+        Format::Toml => without_nulls(v),
+        _ => Some(v.clone()),
+    };
+    let ops: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            let path = entry.path.to_pointer();
+            match &entry.change {
+                Change::Added(v) => Operation {
+                    op: "add",
+                    path,
+                    old: None,
+                    value: keep(v),
+                    delta: None,
+                },
+                Change::Removed(v) => Operation {
+                    op: "remove",
+                    path,
+                    old: keep(v),
+                    value: None,
+                    delta: None,
+                },
+                Change::Changed { old, new } => Operation {
+                    op: "replace",
+                    path,
+                    old: keep(old),
+                    value: keep(new),
+                    delta: entry.delta(),
+                },
+            }
+        })
+        .collect();
+    serialize_list("changes", &ops, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_values() {
+        let old = json!({
+            "kernel": "6.1.0",
+            "disk": [{ "name": "/", "total_bytes": 1000, "used_bytes": 100 }],
+            "packages": { "dpkg": 600, "snap": 3 },
+        });
+        let new = json!({
+            "kernel": "6.8.0",
+            "disk": [
+                { "name": "/home", "total_bytes": 500, "used_bytes": 1 },
+                { "name": "/", "total_bytes": 1000, "used_bytes": 150 },
+            ],
+            "packages": { "dpkg": 612, "snap": 3 },
+        });
+
+        let entries = diff_values(&old, &new);
+        let paths: Vec<_> = entries.iter().map(|e| e.path.to_string()).collect();
+        assert_eq!(
+            paths,
+            [
+                "kernel",
+                "disk(/).used_bytes",
+                "disk(/home)",
+                "packages.dpkg"
+            ]
+        );
+        assert_eq!(entries[1].delta(), Some(50.0));
+        assert_eq!(entries[1].path.to_pointer(), "/disk/~1/used_bytes");
+        assert!(matches!(entries[2].change, Change::Added(_)));
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let entries = diff_values(&json!({ "kernel": "6.1.0" }), &json!({ "kernel": "6.8.0" }));
+        let json: Value = serde_json::from_str(&render(&entries, Format::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["op"], "replace");
+        assert_eq!(json[0]["value"], "6.8.0");
+        let yaml = render(&entries, Format::Yaml).unwrap();
+        assert!(yaml.starts_with("- op: replace\n"));
+        let toml = render(&entries, Format::Toml).unwrap();
+        assert!(toml.starts_with("[[changes]]\nop = \"replace\"\n"));
+        assert!(
+            render(&entries, Format::Text)
+                .unwrap()
+                .contains("6.1.0 -> 6.8.0")
+        );
+
+        // A refresh rate appearing and an interface with no IPv6 address
+        let old = json!({ "display": [{ "name": "DP-1", "refresh_rate_hz": null }] });
+        let new = json!({
+            "display": [{ "name": "DP-1", "refresh_rate_hz": 60.0 }],
+            "network": [{ "interface_name": "eth0", "ipv6_address": null }],
+        });
+        let entries = diff_values(&old, &new);
+        let json: Value = serde_json::from_str(&render(&entries, Format::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["old"], Value::Null);
+        assert_eq!(json[0]["value"], 60.0);
+        let toml: Value = toml::from_str(&render(&entries, Format::Toml).unwrap()).unwrap();
+        let changes = toml["changes"].as_array().unwrap();
+        assert_eq!(changes[0]["path"], "/display/DP-1/refresh_rate_hz");
+        assert_eq!(changes[0].get("old"), None);
+        assert_eq!(changes[0]["value"], 60.0);
+        assert_eq!(changes[1]["value"], json!([{ "interface_name": "eth0" }]));
+    }
+
+    #[test]
+    fn test_format_delta() {
+        assert_eq!(format_delta("used_bytes", 1024.0), "+1 KiB");
+        assert_eq!(format_delta("temperature_celsius", -2.5), "-2.5°C");
+        assert_eq!(format_delta("dpkg", 12.0), "+12");
+    }
+
+    #[test]
+    fn test_diff_redacted() {
+        let (mut old, mut new) = (Neofetch::empty(), Neofetch::empty());
+        old.hostname = Ok("vm".into());
+        new.hostname = Ok("vm2".into());
+        let redact = crate::redact::Redact {
+            enabled: true,
+            hostname: crate::redact::Policy::Hash,
+            ..Default::default()
+        };
+        let entries = diff(&redact.redact(&old), &redact.redact(&new)).unwrap();
+        let text = render_text(&entries);
+        assert_eq!(entries[0].path.to_string(), "hostname");
+        assert!(text.contains("host-"));
+        assert!(!text.contains("vm"));
+    }
+}
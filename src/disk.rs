@@ -3,24 +3,27 @@
 //! Collects disk usage information for mounted filesystems.
 
 use crate::error::{NeofetchError, Result};
-use human_bytes::human_bytes;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// Disk information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Disk {
     /// Disk name or mount point
     pub name: String,
     /// Total disk space in bytes
+    #[serde(rename = "total_bytes")]
     pub total: u64,
     /// Used disk space in bytes
+    #[serde(rename = "used_bytes")]
     pub used: u64,
 }
 
 impl Display for Disk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let used = human_bytes(self.used as f64);
-        let total = human_bytes(self.total as f64);
+        let units = crate::units::current();
+        let used = units.bytes(self.used as f64);
+        let total = units.bytes(self.total as f64);
         let percent = if self.total > 0 {
             (self.used as f64 / self.total as f64) * 100.0
         } else {
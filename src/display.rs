@@ -1,7 +1,10 @@
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Display {
     pub name: Option<String>,
     pub friendly_name: Option<String>,
+    #[serde(rename = "refresh_rate_hz")]
     pub refresh_rate: Option<f32>,
     pub external: Option<bool>,
     pub resolution: Option<(u32, u32)>,
@@ -3,10 +3,12 @@
 //! This module provides a centralized error handling system using thiserror.
 //! All errors in the application should use these types for consistent error handling.
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Main error type for neofetch operations
-#[derive(Error, Debug, Clone)]
+#[derive(Error, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail")]
 pub enum NeofetchError {
     /// Error reading a file
     #[error("Failed to read file '{path}': {message}")]
@@ -29,7 +31,8 @@ pub enum NeofetchError {
     CommandFailed { command: String, code: i32 },
 
     /// Error with WMI query on Windows
-    #[cfg(windows)]
+    ///
+    /// Declared on every platform so snapshots taken on Windows can be loaded elsewhere.
     #[error("WMI query failed: {0}")]
     WmiError(String),
 
@@ -64,6 +67,22 @@ pub enum NeofetchError {
     /// Float parsing error
     #[error("Failed to parse float: {0}")]
     ParseFloat(String),
+
+    /// Error serializing output
+    #[error("Serialization failed: {0}")]
+    Serialization(String),
+
+    /// Operation did not finish in time
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// Error raised by a configured script
+    #[error("Script error: {0}")]
+    Script(String),
+
+    /// Invalid configuration or registration
+    #[error("Invalid configuration: {0}")]
+    Config(String),
 }
 
 // Implement From traits for common error types
@@ -151,6 +170,26 @@ impl NeofetchError {
         Self::SystemCall(message.into())
     }
 
+    /// Create a Serialization error
+    pub fn serialization(message: impl Into<String>) -> Self {
+        Self::Serialization(message.into())
+    }
+
+    /// Create a Timeout error
+    pub fn timeout(message: impl Into<String>) -> Self {
+        Self::Timeout(message.into())
+    }
+
+    /// Create a Script error
+    pub fn script(message: impl Into<String>) -> Self {
+        Self::Script(message.into())
+    }
+
+    /// Create a Config error
+    pub fn config(message: impl Into<String>) -> Self {
+        Self::Config(message.into())
+    }
+
     #[cfg(windows)]
     /// Create a WmiError
     pub fn wmi_error(message: impl Into<String>) -> Self {
@@ -158,6 +197,54 @@ impl NeofetchError {
     }
 }
 
+/// Serde helper for `Result` fields
+///
+/// `Ok` values serialize as the value itself and errors as an `{ error: ... }`
+/// entry, so formats without a null type (TOML) still record why a field is missing.
+pub mod result_serde {
+    use super::{NeofetchError, Result};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct ErrorEntry<'a> {
+        error: &'a NeofetchError,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry<T> {
+        Err { error: NeofetchError },
+        Ok(T),
+    }
+
+    /// Default for fields added after a snapshot was written
+    pub fn missing<T>() -> Result<T> {
+        Err(NeofetchError::data_unavailable("not recorded in snapshot"))
+    }
+
+    pub fn serialize<T, S>(value: &Result<T>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        match value {
+            Ok(v) => v.serialize(serializer),
+            Err(error) => ErrorEntry { error }.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> std::result::Result<Result<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(match Entry::deserialize(deserializer)? {
+            Entry::Err { error } => Err(error),
+            Entry::Ok(v) => Ok(v),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +255,28 @@ mod tests {
         assert_eq!(err.to_string(), "Failed to parse cpu_speed: invalid number");
     }
 
+    #[test]
+    fn test_result_serde_roundtrip() {
+        #[derive(Serialize, Deserialize)]
+        struct Field {
+            #[serde(with = "result_serde")]
+            value: Result<u32>,
+        }
+
+        let err = Field {
+            value: Err(NeofetchError::command_failed("opkg", 127)),
+        };
+        let json = serde_json::to_string(&err).unwrap();
+        let back: Field = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            back.value.unwrap_err().to_string(),
+            "Command 'opkg' failed with exit code 127"
+        );
+
+        let ok: Field = serde_json::from_str(r#"{"value":42}"#).unwrap();
+        assert_eq!(ok.value.unwrap(), 42);
+    }
+
     #[test]
     fn test_data_unavailable() {
         let err = NeofetchError::data_unavailable("GPU information");
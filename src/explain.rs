@@ -0,0 +1,298 @@
+//! Per-module diagnostics
+//!
+//! Explains why a line is missing from the output: every module is collected on
+//! its own and listed with its status, the error it returned, and the files it
+//! read and the commands it ran.
+//!
+//! Only reads and commands going through the [`utils`](crate::utils) helpers are
+//! recorded. The shell, terminal and display probes made inside external crates
+//! (`which-shell`, `which-terminal`, `display-info`) are not listed.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Neofetch;
+use crate::color::{BOLD, DIM, GREEN, RED, RESET, YELLOW};
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+use crate::output::{Format, serialize_list};
+use crate::redact::Scrubber;
+use crate::utils::timing::{Recorded, start_recording, take_recorded};
+use crate::utils::width;
+
+/// Outcome of a collector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    /// Not implemented for this platform
+    UnsupportedPlatform,
+    /// Implemented, but the file, command or API it relies on gave nothing
+    DataUnavailable,
+    ParseError,
+    Timeout,
+    /// Any other failure, such as a command exiting with an error
+    Error,
+}
+
+impl Status {
+    /// Status of a collected value
+    pub fn of(result: &Result<Value>) -> Status {
+        match result {
+            Ok(_) => Status::Ok,
+            Err(e) => match e {
+                NeofetchError::UnsupportedPlatform => Status::UnsupportedPlatform,
+                NeofetchError::DataUnavailable(_)
+                | NeofetchError::FileRead { .. }
+                | NeofetchError::CommandExecution { .. } => Status::DataUnavailable,
+                NeofetchError::ParseError { .. }
+                | NeofetchError::ParseInt(_)
+                | NeofetchError::ParseFloat(_)
+                | NeofetchError::Utf8Error(_) => Status::ParseError,
+                NeofetchError::Timeout(_) => Status::Timeout,
+                _ => Status::Error,
+            },
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::UnsupportedPlatform => "unsupported platform",
+            Status::DataUnavailable => "data unavailable",
+            Status::ParseError => "parse error",
+            Status::Timeout => "timeout",
+            Status::Error => "error",
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self {
+            Status::Ok => GREEN,
+            Status::UnsupportedPlatform | Status::DataUnavailable => YELLOW,
+            _ => RED,
+        }
+    }
+}
+
+/// What happened while collecting one module
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Explanation {
+    pub module: String,
+    pub status: Status,
+    /// Error message, when the module failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: f64,
+    pub files: Vec<String>,
+    pub commands: Vec<String>,
+}
+
+fn explanation(
+    neofetch: &Neofetch,
+    module: Module,
+    elapsed: Duration,
+    recorded: Recorded,
+) -> Explanation {
+    let value = neofetch.value(module);
+    Explanation {
+        module: module.name().to_string(),
+        status: Status::of(&value),
+        error: value.err().map(|e| e.to_string()),
+        duration_ms: elapsed.as_micros() as f64 / 1000.0,
+        files: recorded.files,
+        commands: recorded.commands.into_iter().map(|(c, _)| c).collect(),
+    }
+}
+
+/// Collect every module one after another, so each file and command is
+/// attributed to the module that used it, then run the scripts on the result
+pub async fn explain() -> Vec<Explanation> {
+    let mut neofetch = Neofetch::empty();
+    let scripts = crate::script::names();
+    let mut explanations = Vec::new();
+    for module in Module::all() {
+        // Script lines only get a value when their script runs, below
+        if scripts.contains(&module.name()) {
+            continue;
+        }
+        start_recording();
+        let start = Instant::now();
+        neofetch.refresh(&[module]).await;
+        let elapsed = start.elapsed();
+        explanations.push(explanation(&neofetch, module, elapsed, take_recorded()));
+    }
+    for name in scripts {
+        start_recording();
+        let start = Instant::now();
+        crate::script::run(name, &mut neofetch);
+        let elapsed = start.elapsed();
+        let module = Module::Extra(name);
+        explanations.push(explanation(&neofetch, module, elapsed, take_recorded()));
+    }
+    if let Some(scrubber) = crate::redact::scrubber(&neofetch) {
+        scrub(&mut explanations, &scrubber);
+    }
+    explanations
+}
+
+/// Replace identifying data in the errors, paths and command lines
+pub fn scrub(explanations: &mut [Explanation], scrubber: &Scrubber) {
+    for e in explanations {
+        e.error = e.error.as_deref().map(|error| scrubber.text(error));
+        for text in e.files.iter_mut().chain(&mut e.commands) {
+            *text = scrubber.text(text);
+        }
+    }
+}
+
+/// One block per module, with its error, files and commands indented below
+pub fn to_text(explanations: &[Explanation]) -> String {
+    let module_width = explanations
+        .iter()
+        .map(|e| width(&e.module))
+        .chain([width("MODULE")])
+        .max()
+        .unwrap_or(0);
+    let status_width = explanations
+        .iter()
+        .map(|e| e.status.label().len())
+        .chain([width("STATUS")])
+        .max()
+        .unwrap_or(0);
+    let pad = |s: &str, w: usize| format!("{s}{}", " ".repeat(w.saturating_sub(width(s))));
+
+    let mut lines = vec![format!(
+        "{GREEN}{BOLD}{}  {}  TIME{RESET}",
+        pad("MODULE", module_width),
+        pad("STATUS", status_width)
+    )];
+    let indent = " ".repeat(module_width + 2);
+    for e in explanations {
+        lines.push(format!(
+            "{}  {}{}{RESET}  {:>9.2} ms",
+            pad(&e.module, module_width),
+            e.status.color(),
+            pad(e.status.label(), status_width),
+            e.duration_ms
+        ));
+        if let Some(error) = &e.error {
+            lines.push(format!("{indent}{error}"));
+        }
+        for file in &e.files {
+            lines.push(format!("{indent}{DIM}read{RESET}  {file}"));
+        }
+        for command in &e.commands {
+            lines.push(format!("{indent}{DIM}run{RESET}   {command}"));
+        }
+    }
+    let ok = explanations
+        .iter()
+        .filter(|e| e.status == Status::Ok)
+        .count();
+    lines.push(format!(
+        "{GREEN}{BOLD}{ok} of {} modules collected{RESET}",
+        explanations.len()
+    ));
+    lines.join("\n")
+}
+
+/// Render the explanations as a text table or a serialized list
+pub fn render(explanations: &[Explanation], format: Format) -> Result<String> {
+    match format {
+        Format::Text => Ok(to_text(explanations)),
+        _ => serialize_list("modules", explanations, format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        assert_eq!(Status::of(&Ok(Value::Null)), Status::Ok);
+        let status = |e: NeofetchError| Status::of(&Err(e));
+        assert_eq!(
+            status(NeofetchError::UnsupportedPlatform),
+            Status::UnsupportedPlatform
+        );
+        assert_eq!(
+            status(NeofetchError::data_unavailable("no battery")),
+            Status::DataUnavailable
+        );
+        assert_eq!(
+            status(NeofetchError::parse_error("uptime", "not a number")),
+            Status::ParseError
+        );
+        assert_eq!(status(NeofetchError::timeout("ip")), Status::Timeout);
+        assert_eq!(
+            status(NeofetchError::command_failed("xrandr", 1)),
+            Status::Error
+        );
+    }
+
+    #[test]
+    fn test_to_text() {
+        let explanations = vec![
+            Explanation {
+                module: "kernel".into(),
+                status: Status::Ok,
+                error: None,
+                duration_ms: 0.5,
+                files: vec!["/proc/sys/kernel/osrelease".into()],
+                commands: vec![],
+            },
+            Explanation {
+                module: "battery".into(),
+                status: Status::DataUnavailable,
+                error: Some("Data not available: no battery".into()),
+                duration_ms: 1.25,
+                files: vec![],
+                commands: vec!["upower -e".into()],
+            },
+        ];
+        let ansi = regex::Regex::new("\x1b\\[[0-9;]*m").unwrap();
+        let text = ansi.replace_all(&to_text(&explanations), "").into_owned();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines[0], "MODULE   STATUS            TIME");
+        assert_eq!(lines[1], "kernel   ok                     0.50 ms");
+        assert_eq!(lines[2], "         read  /proc/sys/kernel/osrelease");
+        assert_eq!(lines[3], "battery  data unavailable       1.25 ms");
+        assert_eq!(lines[4], "         Data not available: no battery");
+        assert_eq!(lines[5], "         run   upower -e");
+        assert_eq!(lines[6], "1 of 2 modules collected");
+
+        let toml = render(&explanations, Format::Toml).unwrap();
+        assert!(toml.starts_with("[[modules]]\nmodule = \"kernel\"\n"));
+    }
+
+    #[test]
+    fn test_scrub() {
+        let mut explanations = vec![Explanation {
+            module: "hostname".into(),
+            status: Status::Error,
+            error: Some("Command 'ping workstation-7' failed with exit code 1".into()),
+            duration_ms: 0.0,
+            files: vec!["/home/alice/.config/neofetch/config.toml".into()],
+            commands: vec!["ping workstation-7".into()],
+        }];
+        let mut neofetch = Neofetch::empty();
+        neofetch.hostname = Ok("workstation-7".into());
+        neofetch.user = Ok("alice".into());
+        let redact = crate::redact::Redact {
+            enabled: true,
+            ..Default::default()
+        };
+        scrub(&mut explanations, &redact.scrubber(&neofetch));
+        let e = &explanations[0];
+        assert_eq!(
+            e.error.as_deref(),
+            Some("Command 'ping hostname' failed with exit code 1")
+        );
+        assert_eq!(e.files[0], "/home/user/.config/neofetch/config.toml");
+        assert_eq!(e.commands[0], "ping hostname");
+    }
+}
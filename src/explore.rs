@@ -0,0 +1,525 @@
+//! Interactive explorer of the collected data
+//!
+//! The info lines only summarize each module. The explorer lists every module
+//! in a sidebar and shows the selected one in full (every interface, sensor,
+//! mount and display) as YAML.
+//!
+//! Keys: `↑`/`↓` (or `k`/`j`) select a module, `PgUp`/`PgDn`/`Space` and
+//! `Home`/`End` scroll the details, `/` filters modules by name or content,
+//! `e` exports the selected module in the `--format` (text exports the view
+//! as shown), `r` collects it again and `q` quits.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use unicode_width::UnicodeWidthChar;
+
+use crate::Neofetch;
+use crate::color::{
+    ALT_SCREEN, BOLD, CLEAR_LINE, CURSOR_HOME, DIM, HIDE_CURSOR, MAIN_SCREEN, RED, RESET, REVERSE,
+    SHOW_CURSOR, YELLOW,
+};
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+use crate::output::{Format, serialize, to_yaml};
+
+/// Keys understood by the explorer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Esc,
+    Backspace,
+    Interrupt,
+    Char(char),
+}
+
+/// What the event loop does after a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    Quit,
+    Export,
+    Refresh(Module),
+}
+
+/// Keys in the bytes read from a raw terminal
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut chars = text.chars().peekable();
+    let mut keys = Vec::new();
+    while let Some(c) = chars.next() {
+        let key = match c {
+            '\x1b' if matches!(chars.peek(), Some('[' | 'O')) => {
+                chars.next();
+                let mut seq = String::new();
+                for c in chars.by_ref() {
+                    seq.push(c);
+                    if c.is_ascii_alphabetic() || c == '~' {
+                        break;
+                    }
+                }
+                match seq.as_str() {
+                    "A" => Key::Up,
+                    "B" => Key::Down,
+                    "5~" => Key::PageUp,
+                    "6~" => Key::PageDown,
+                    "H" | "1~" => Key::Home,
+                    "F" | "4~" => Key::End,
+                    _ => continue,
+                }
+            }
+            '\x1b' => Key::Esc,
+            '\r' | '\n' => Key::Enter,
+            '\x7f' | '\x08' => Key::Backspace,
+            '\x03' => Key::Interrupt,
+            c if c.is_control() => continue,
+            c => Key::Char(c),
+        };
+        keys.push(key);
+    }
+    keys
+}
+
+struct Entry {
+    module: Module,
+    value: Result<Value>,
+    lines: Vec<String>,
+}
+
+impl Entry {
+    fn new(module: Module, value: Result<Value>) -> Self {
+        let text = match &value {
+            Ok(value) => to_yaml(value).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("error: {e}"),
+        };
+        let lines = text.lines().map(str::to_string).collect();
+        Entry {
+            module,
+            value,
+            lines,
+        }
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        self.module.name().contains(query)
+            || self.lines.iter().any(|l| l.to_lowercase().contains(query))
+    }
+}
+
+/// State of the explorer: modules, selection, scroll position and search
+pub struct Explorer {
+    entries: Vec<Entry>,
+    /// Indices of the entries matching the search
+    visible: Vec<usize>,
+    /// Position of the selected module in `visible`
+    selected: usize,
+    scroll: usize,
+    query: String,
+    searching: bool,
+    status: String,
+    format: Format,
+}
+
+impl Explorer {
+    /// Explorer over every module of `neofetch` as it is shown, exporting in `format`
+    pub fn new(neofetch: &Neofetch, format: Format) -> Self {
+        let neofetch = &*crate::output::prepare(neofetch);
+        let entries = Module::all()
+            .into_iter()
+            .map(|m| (m, neofetch.value(m)))
+            .collect();
+        Explorer::from_entries(entries, format)
+    }
+
+    fn from_entries(entries: Vec<(Module, Result<Value>)>, format: Format) -> Self {
+        let entries: Vec<Entry> = entries.into_iter().map(|(m, v)| Entry::new(m, v)).collect();
+        Explorer {
+            visible: (0..entries.len()).collect(),
+            entries,
+            selected: 0,
+            scroll: 0,
+            query: String::new(),
+            searching: false,
+            status: String::new(),
+            format,
+        }
+    }
+
+    fn current(&self) -> Option<&Entry> {
+        self.visible.get(self.selected).map(|&i| &self.entries[i])
+    }
+
+    /// Selected module, `None` when the search matches nothing
+    pub fn selected(&self) -> Option<Module> {
+        self.current().map(|e| e.module)
+    }
+
+    /// Show the status message until the next key
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = status.into();
+    }
+
+    /// Replace the data after a module was collected again; every entry is
+    /// updated, since script lines may depend on the module
+    pub fn update(&mut self, neofetch: &Neofetch) {
+        let neofetch = &*crate::output::prepare(neofetch);
+        for entry in &mut self.entries {
+            *entry = Entry::new(entry.module, neofetch.value(entry.module));
+        }
+    }
+
+    fn select(&mut self, position: usize) {
+        self.selected = position.min(self.visible.len().saturating_sub(1));
+        self.scroll = 0;
+    }
+
+    fn filter(&mut self) {
+        let selected = self.selected();
+        let query = self.query.to_lowercase();
+        self.visible = (0..self.entries.len())
+            .filter(|&i| self.entries[i].matches(&query))
+            .collect();
+        let position = self
+            .visible
+            .iter()
+            .position(|&i| Some(self.entries[i].module) == selected);
+        self.select(position.unwrap_or(0));
+    }
+
+    /// Apply a key; `page` is the number of detail lines on screen
+    pub fn handle(&mut self, key: Key, page: usize) -> Action {
+        self.status.clear();
+        if self.searching {
+            match key {
+                Key::Enter => self.searching = false,
+                Key::Esc => {
+                    self.searching = false;
+                    self.query.clear();
+                }
+                Key::Backspace => {
+                    self.query.pop();
+                }
+                Key::Char(c) => self.query.push(c),
+                Key::Interrupt => return Action::Quit,
+                _ => return Action::Continue,
+            }
+            self.filter();
+            return Action::Continue;
+        }
+
+        let lines = self.current().map_or(0, |e| e.lines.len());
+        let bottom = lines.saturating_sub(page);
+        match key {
+            Key::Up | Key::Char('k') => self.select(self.selected.saturating_sub(1)),
+            Key::Down | Key::Char('j') => self.select(self.selected + 1),
+            Key::PageUp => self.scroll = self.scroll.saturating_sub(page),
+            Key::PageDown | Key::Char(' ') => self.scroll = (self.scroll + page).min(bottom),
+            Key::Home => self.scroll = 0,
+            Key::End => self.scroll = bottom,
+            Key::Char('/') => self.searching = true,
+            Key::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.filter();
+            }
+            Key::Char('e') if self.current().is_some() => return Action::Export,
+            Key::Char('r') => {
+                if let Some(module) = self.selected() {
+                    return Action::Refresh(module);
+                }
+            }
+            Key::Char('q') | Key::Esc | Key::Interrupt => return Action::Quit,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Write the selected module to `neofetch-<module>.<format>` in the current directory
+    pub fn export(&self) -> Result<PathBuf> {
+        let entry = self
+            .current()
+            .ok_or_else(|| NeofetchError::data_unavailable("no module selected"))?;
+        let name = entry.module.name();
+        let (extension, content) = match self.format {
+            Format::Text => ("txt".to_string(), entry.lines.join("\n") + "\n"),
+            format => {
+                // Failed modules are written like in snapshots
+                let value = match &entry.value {
+                    Ok(value) => value.clone(),
+                    Err(e) => serde_json::json!({ "error": e }),
+                };
+                let document = serde_json::json!({ name: value });
+                (format.to_string(), serialize(&document, format)?)
+            }
+        };
+        let path = PathBuf::from(format!("neofetch-{name}.{extension}"));
+        std::fs::write(&path, content)
+            .map_err(|e| NeofetchError::file_write(path.display().to_string(), e))?;
+        Ok(path)
+    }
+
+    /// Frame of `width` × `height` cells: a title, the sidebar and details, and a status line
+    pub fn render(&self, width: usize, height: usize) -> String {
+        let rows = height.saturating_sub(2);
+        let names = self.entries.iter().map(|e| e.module.name().len());
+        let sidebar = (names.max().unwrap_or(0) + 4).min(width / 3);
+        let detail = width.saturating_sub(sidebar + 1);
+        let entry = self.current();
+
+        let title = match entry {
+            Some(e) => format!(
+                " neofetch explore │ {} ({}/{})",
+                e.module,
+                self.selected + 1,
+                self.visible.len()
+            ),
+            None => " neofetch explore │ no matches".to_string(),
+        };
+        let mut lines = vec![format!("{REVERSE}{BOLD}{}{RESET}", fit(&title, width))];
+
+        // Keep the selected module on screen
+        let offset = self.selected.saturating_sub(rows.saturating_sub(1));
+        let query = self.query.to_lowercase();
+        for row in 0..rows {
+            let side = match self.visible.get(offset + row) {
+                Some(&i) => {
+                    let e = &self.entries[i];
+                    let marker = if e.value.is_err() { "!" } else { " " };
+                    let text = fit(&format!(" {marker}{}", e.module), sidebar);
+                    match (offset + row == self.selected, e.value.is_err()) {
+                        (true, _) => format!("{REVERSE}{text}{RESET}"),
+                        (false, true) => format!("{DIM}{text}{RESET}"),
+                        (false, false) => text,
+                    }
+                }
+                None => " ".repeat(sidebar),
+            };
+            let body = match entry.and_then(|e| e.lines.get(self.scroll + row)) {
+                Some(line) => {
+                    let text = fit(&format!(" {line}"), detail);
+                    match !query.is_empty() && line.to_lowercase().contains(&query) {
+                        true => format!("{YELLOW}{BOLD}{text}{RESET}"),
+                        false if line.starts_with("error:") => format!("{RED}{text}{RESET}"),
+                        false => text,
+                    }
+                }
+                None => String::new(),
+            };
+            lines.push(format!("{side}{DIM}│{RESET}{body}"));
+        }
+
+        let status = if self.searching {
+            format!("/{}", self.query)
+        } else if !self.status.is_empty() {
+            self.status.clone()
+        } else if !self.query.is_empty() {
+            format!("filter: {}  (esc clears)", self.query)
+        } else {
+            "↑↓ module  PgUp/PgDn scroll  / search  e export  r refresh  q quit".to_string()
+        };
+        lines.push(format!("{DIM}{}{RESET}", fit(&status, width)));
+
+        let mut frame = String::from(CURSOR_HOME);
+        for line in lines.iter().take(height) {
+            frame.push_str(line);
+            frame.push_str(CLEAR_LINE);
+            frame.push_str("\r\n");
+        }
+        // No newline after the last row, which would scroll the screen
+        frame.truncate(frame.len() - 2);
+        frame
+    }
+}
+
+/// `text` cut or padded with spaces to exactly `columns` cells
+fn fit(text: &str, columns: usize) -> String {
+    let mut s = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > columns {
+            break;
+        }
+        s.push(c);
+        used += w;
+    }
+    s.push_str(&" ".repeat(columns - used));
+    s
+}
+
+/// Terminal in raw mode on the alternate screen, restored when dropped
+#[cfg(unix)]
+struct Screen {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl Screen {
+    fn enter() -> Result<Self> {
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        // Keys arrive one by one, unechoed, and Ctrl-C is read as a key
+        let mut raw = original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_iflag &= !(libc::IXON | libc::ICRNL);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        let mut stdout = std::io::stdout();
+        write!(stdout, "{ALT_SCREEN}{HIDE_CURSOR}")?;
+        stdout.flush()?;
+        Ok(Screen { original })
+    }
+
+    /// Columns and rows of the terminal
+    fn size(&self) -> (usize, usize) {
+        crate::watch::terminal_size().unwrap_or((80, 24))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let mut stdout = std::io::stdout();
+        let _ = write!(stdout, "{SHOW_CURSOR}{MAIN_SCREEN}");
+        let _ = stdout.flush();
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original) };
+    }
+}
+
+/// Browse `neofetch` until the user quits
+#[cfg(unix)]
+pub async fn explore(mut neofetch: Neofetch, format: Format) -> Result<()> {
+    use std::io::IsTerminal;
+    use tokio::io::AsyncReadExt;
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(NeofetchError::Io(
+            "explore needs an interactive terminal".into(),
+        ));
+    }
+    let mut explorer = Explorer::new(&neofetch, format);
+    let screen = Screen::enter()?;
+    let mut stdin = tokio::io::stdin();
+    let mut stdout = std::io::stdout();
+    let mut buf = [0u8; 64];
+    loop {
+        let (width, height) = screen.size();
+        write!(stdout, "{}", explorer.render(width, height))?;
+        stdout.flush()?;
+
+        let n = stdin.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        for key in parse_keys(&buf[..n]) {
+            match explorer.handle(key, height.saturating_sub(2)) {
+                Action::Continue => {}
+                Action::Quit => return Ok(()),
+                Action::Export => {
+                    let status = match explorer.export() {
+                        Ok(path) => format!("saved {}", path.display()),
+                        Err(e) => e.to_string(),
+                    };
+                    explorer.set_status(status);
+                }
+                Action::Refresh(module) => {
+                    neofetch.refresh(&[module]).await;
+                    explorer.update(&neofetch);
+                    explorer.set_status(format!("refreshed {module}"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn explore(_neofetch: Neofetch, _format: Format) -> Result<()> {
+    Err(NeofetchError::UnsupportedPlatform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn explorer() -> Explorer {
+        let network = json!([
+            { "interface_name": "lo", "ipv4_address": "127.0.0.1", "is_up": true },
+            { "interface_name": "eth0", "ipv4_address": "192.0.2.2", "is_up": true },
+        ]);
+        Explorer::from_entries(
+            vec![
+                (Module::Cpu, Ok(json!({ "name": "Ryzen", "cores": 8 }))),
+                (Module::Gpu, Err(NeofetchError::UnsupportedPlatform)),
+                (Module::Network, Ok(network)),
+            ],
+            Format::Json,
+        )
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        let keys = parse_keys(b"\x1b[A\x1b[6~j/\x7f\r\x1b\x03");
+        assert_eq!(
+            keys,
+            [
+                Key::Up,
+                Key::PageDown,
+                Key::Char('j'),
+                Key::Char('/'),
+                Key::Backspace,
+                Key::Enter,
+                Key::Esc,
+                Key::Interrupt
+            ]
+        );
+    }
+
+    #[test]
+    fn test_navigation_and_search() {
+        let mut explorer = explorer();
+        assert_eq!(explorer.handle(Key::Down, 5), Action::Continue);
+        assert_eq!(explorer.selected(), Some(Module::Gpu));
+        assert_eq!(
+            explorer.handle(Key::Char('r'), 5),
+            Action::Refresh(Module::Gpu)
+        );
+
+        // Searching matches content, not only names
+        for key in parse_keys(b"/eth0\r") {
+            explorer.handle(key, 5);
+        }
+        assert_eq!(explorer.selected(), Some(Module::Network));
+        assert_eq!(explorer.handle(Key::Down, 5), Action::Continue);
+        assert_eq!(explorer.selected(), Some(Module::Network));
+        explorer.handle(Key::End, 5);
+        assert_eq!(explorer.scroll, explorer.current().unwrap().lines.len() - 5);
+
+        assert_eq!(explorer.handle(Key::Esc, 5), Action::Continue);
+        assert_eq!(explorer.visible.len(), 3);
+        assert_eq!(explorer.handle(Key::Char('q'), 5), Action::Quit);
+    }
+
+    #[test]
+    fn test_render() {
+        let frame = explorer().render(40, 6);
+        let lines: Vec<_> = frame.split("\r\n").collect();
+        assert_eq!(lines.len(), 6);
+        for line in &lines {
+            assert!(crate::utils::width(line) <= 40);
+        }
+        assert!(lines[1].contains("cpu") && lines[1].contains("name: Ryzen"));
+        assert!(lines[2].contains("!gpu"));
+    }
+}
@@ -0,0 +1,87 @@
+//! Icons shown next to (or instead of) the info labels
+//!
+//! The Nerd Font set needs a patched font in the terminal; the emoji set works
+//! with any font that has color emoji.
+
+use serde::Deserialize;
+
+use crate::module::Module;
+
+/// Icon set of the info labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    #[default]
+    None,
+    #[serde(alias = "nerd-font", alias = "nerdfont")]
+    Nerd,
+    Emoji,
+}
+
+impl IconSet {
+    /// Icon of a module in this set
+    pub fn icon(self, module: Module) -> Option<&'static str> {
+        let (nerd, emoji) = match module {
+            Module::Os => ("\u{f17c}", "🐧"),
+            Module::User => ("\u{f007}", "👤"),
+            Module::Host => ("\u{f0322}", "💻"),
+            Module::Hostname => ("\u{f048b}", "🏷️"),
+            Module::Rom => ("\u{f10b}", "📱"),
+            Module::Baseband => ("\u{f043b}", "📡"),
+            Module::Kernel => ("\u{f013}", "⚙️"),
+            Module::Uptime => ("\u{f0150}", "⏱️"),
+            Module::LoadAverage => ("\u{f029a}", "📈"),
+            Module::Packages => ("\u{f03d6}", "📦"),
+            Module::Shell => ("\u{f489}", "🐚"),
+            Module::Display => ("\u{f0379}", "🖥️"),
+            Module::De => ("\u{f108}", "🪟"),
+            Module::Wm => ("\u{f2d2}", "🪟"),
+            Module::WmTheme => ("\u{f03d8}", "🎨"),
+            Module::Terminal => ("\u{e795}", "⌨️"),
+            Module::Disk => ("\u{f02ca}", "💾"),
+            Module::Cpu => ("\u{f4bc}", "🧠"),
+            Module::Gpu => ("\u{f08ae}", "🎮"),
+            Module::Memory => ("\u{f035b}", "🐏"),
+            Module::Battery => ("\u{f0079}", "🔋"),
+            Module::Locale => ("\u{f1ab}", "🌍"),
+            Module::Ip => ("\u{f059f}", "🌐"),
+            Module::LocalIp => ("\u{f0a5f}", "🏠"),
+            Module::Temperature => ("\u{f2c9}", "🌡️"),
+            Module::Network => ("\u{f06f3}", "📶"),
+            Module::Extra(_) => return None,
+        };
+        match self {
+            IconSet::None => None,
+            IconSet::Nerd => Some(nerd),
+            IconSet::Emoji => Some(emoji),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_icon_sets_cover_builtins() {
+        for module in Module::ALL {
+            assert!(IconSet::Nerd.icon(module).is_some());
+            assert!(IconSet::Emoji.icon(module).is_some());
+            assert_eq!(IconSet::None.icon(module), None);
+        }
+        assert_eq!(IconSet::Emoji.icon(Module::Extra("build")), None);
+    }
+
+    #[test]
+    fn test_icons_config() {
+        let config = Config::parse("info = { icons = 'emoji', icon = { cpu = '*' } }").unwrap();
+        assert_eq!(
+            config.info.label(Module::Memory, "Memory".into()),
+            "🐏 Memory"
+        );
+        assert_eq!(config.info.label(Module::Cpu, "CPU".into()), "* CPU");
+        assert_eq!(config.info.label(Module::Cpu, String::new()), "");
+        assert!(Config::parse("info.icon.nope = '*'").is_err());
+    }
+}
@@ -1,20 +1,24 @@
 use std::fmt::Display;
 
-use human_bytes::human_bytes;
+use serde::{Deserialize, Serialize};
 
 use crate::error::{NeofetchError, Result};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Gpu {
     pub name: String,
     pub version: String,
+    #[serde(rename = "ram_bytes")]
     pub ram: u64,
 }
 impl Display for Gpu {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut parts: Vec<String> = vec![self.name.clone()];
         if self.ram > 0 {
-            parts.push(format!("({})", human_bytes(self.ram as f64)));
+            parts.push(format!(
+                "({})",
+                crate::units::current().bytes(self.ram as f64)
+            ));
         }
         if !self.version.is_empty() {
             if self.ram > 0 {
@@ -102,7 +106,8 @@ fn load_pci_ids() -> (
     std::collections::HashMap<String, String>,
     std::collections::HashMap<(String, String), String>,
 ) {
-    let content = std::fs::read_to_string("/usr/share/misc/pci.ids").unwrap_or_default();
+    let content =
+        crate::utils::read_file_to_string_sync("/usr/share/misc/pci.ids").unwrap_or_default();
     let mut vendors = std::collections::HashMap::new();
     let mut devices = std::collections::HashMap::new();
 
@@ -138,6 +143,7 @@ pub async fn get_gpu() -> Result<Vec<Gpu>> {
     let (vendor_names, device_names) = load_pci_ids();
 
     let mut v = vec![];
+    crate::utils::timing::record_file(path);
     let mut dir = tokio::fs::read_dir(path)
         .await
         .map_err(|e| NeofetchError::file_read(path.display().to_string(), e))?;
@@ -148,10 +154,10 @@ pub async fn get_gpu() -> Result<Vec<Gpu>> {
     {
         let device_path = entry.path();
 
-        let vendor = tokio::fs::read_to_string(device_path.join("vendor"))
+        let vendor = crate::utils::read_file_to_string(device_path.join("vendor"))
             .await
             .unwrap_or_default();
-        let device = tokio::fs::read_to_string(device_path.join("device"))
+        let device = crate::utils::read_file_to_string(device_path.join("device"))
             .await
             .unwrap_or_default();
 
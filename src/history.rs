@@ -0,0 +1,501 @@
+//! Local history of runs and trend views
+//!
+//! Runs of the live system append a small record (time, boot time, kernel,
+//! uptime, package count and disk usage) to
+//! `$XDG_DATA_HOME/neofetch/history.jsonl`, one JSON object per line. A run
+//! within [`History::interval`] of the last record adds nothing, and only the
+//! latest [`History::max_records`] are kept. The views built from it:
+//!
+//! - `disks`: usage growth per mount point since it was first recorded
+//! - `packages`: package count over time
+//! - `kernels`: when each kernel was first seen
+//! - `uptimes`: the longest uptimes, one per boot, like `uptimed`
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Neofetch;
+use crate::color::{BOLD, GREEN, RESET};
+use crate::disk::Disk;
+use crate::error::{NeofetchError, Result};
+use crate::output::{Format, serialize_list};
+use crate::redact::Scrubber;
+use crate::uptime::Time;
+
+/// Uptime records shown by [`View::Uptimes`]
+const TOP_UPTIMES: usize = 10;
+
+/// History settings
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct History {
+    /// Append a record of the runs
+    pub record: bool,
+    /// Minimum time between two records, so frequent runs (e.g. from a shell
+    /// startup file) do not grow the history
+    #[serde(deserialize_with = "crate::config::duration")]
+    pub interval: Option<Duration>,
+    /// Records kept; the oldest are dropped beyond it
+    pub max_records: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History {
+            record: true,
+            interval: Some(Duration::from_secs(3600)),
+            max_records: 10_000,
+        }
+    }
+}
+
+impl History {
+    /// Whether a run now should be recorded, judged by when the history file
+    /// was last written so that no record has to be read
+    pub fn due(&self) -> bool {
+        if !self.record {
+            return false;
+        }
+        let Some(interval) = self.interval else {
+            return true;
+        };
+        let modified = path()
+            .and_then(|path| std::fs::metadata(path).ok())
+            .and_then(|metadata| metadata.modified().ok());
+        match modified.and_then(|modified| modified.elapsed().ok()) {
+            Some(age) => age >= interval,
+            None => true,
+        }
+    }
+}
+
+/// One run of the live system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    /// Unix time of the run
+    pub time: i64,
+    /// Unix time of the boot, which tells the uptimes of different boots apart
+    pub boot_time: Option<i64>,
+    pub kernel: Option<String>,
+    pub uptime: Option<Time>,
+    pub packages: Option<usize>,
+    #[serde(default)]
+    pub disks: Vec<Disk>,
+}
+
+impl Record {
+    /// Record of a collected snapshot taken now
+    pub async fn new(neofetch: &Neofetch) -> Record {
+        let time = chrono::Utc::now().timestamp();
+        let uptime = neofetch.uptime.as_ref().ok().copied();
+        // Without /proc/stat derive it from the uptime, to the minute as it jitters
+        let boot_time = match crate::system::get_boot_time().await {
+            Ok(boot_time) => Some(boot_time),
+            Err(_) => uptime.map(|u| (time - u.0 as i64) / 60 * 60),
+        };
+        Record {
+            time,
+            boot_time,
+            kernel: neofetch.kernel.as_ref().ok().cloned(),
+            uptime,
+            packages: neofetch.packages.as_ref().ok().map(|p| p.total()),
+            disks: neofetch.disk.as_ref().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/// Location of the history file
+pub fn path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("neofetch").join("history.jsonl"))
+}
+
+/// Append a record to the history file, dropping the oldest beyond `max_records`
+pub fn append(record: &Record, max_records: usize) -> Result<()> {
+    let path = path().ok_or_else(|| NeofetchError::data_unavailable("no data directory"))?;
+    let write_error = |e| NeofetchError::file_write(path.display().to_string(), e);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(write_error)?;
+    }
+    let line =
+        serde_json::to_string(record).map_err(|e| NeofetchError::serialization(e.to_string()))?;
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(NeofetchError::file_read(path.display().to_string(), e)),
+    };
+    if let Some(kept) = compact(&content, max_records) {
+        // Replace the file at once, so an interrupted run cannot truncate it
+        let tmp = path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, format!("{kept}{line}\n")).map_err(write_error)?;
+        return std::fs::rename(&tmp, &path).map_err(write_error);
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(write_error)?;
+    writeln!(file, "{line}").map_err(write_error)
+}
+
+/// The latest lines of `content` that leave room for one more record, or
+/// `None` when all of them fit
+fn compact(content: &str, max_records: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let keep = max_records.max(1) - 1;
+    if lines.len() <= keep {
+        return None;
+    }
+    Some(
+        lines[lines.len() - keep..]
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect(),
+    )
+}
+
+/// Records of the history file, oldest first; unreadable lines are skipped
+pub fn load() -> Result<Vec<Record>> {
+    let Some(path) = path() else {
+        return Ok(Vec::new());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(content) => Ok(parse(&content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(NeofetchError::file_read(path.display().to_string(), e)),
+    }
+}
+
+fn parse(content: &str) -> Vec<Record> {
+    let mut records: Vec<Record> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    records.sort_by_key(|r| r.time);
+    records
+}
+
+/// Trend view of the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    Disks,
+    Packages,
+    Kernels,
+    Uptimes,
+}
+
+impl FromStr for View {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "disks" | "disk" => Ok(View::Disks),
+            "packages" => Ok(View::Packages),
+            "kernels" | "kernel" => Ok(View::Kernels),
+            "uptimes" | "uptime" => Ok(View::Uptimes),
+            _ => Err(format!(
+                "unknown view '{s}' (expected disks, packages, kernels or uptimes)"
+            )),
+        }
+    }
+}
+
+impl Display for View {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            View::Disks => "disks",
+            View::Packages => "packages",
+            View::Kernels => "kernels",
+            View::Uptimes => "uptimes",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Rows of a view under their column headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Local date and time of a Unix time
+fn date(time: i64) -> String {
+    use chrono::TimeZone;
+    match chrono::Local.timestamp_opt(time, 0).single() {
+        Some(dt) => dt.format("%Y-%m-%d %H:%M").to_string(),
+        None => time.to_string(),
+    }
+}
+
+/// A byte difference with its sign, e.g. `+1.2 GiB`
+fn growth(bytes: i64) -> String {
+    let sign = if bytes < 0 { "-" } else { "+" };
+    let size = crate::units::current().bytes(bytes.unsigned_abs() as f64);
+    format!("{sign}{size}")
+}
+
+impl View {
+    /// The view of `records`, oldest first
+    pub fn table(self, records: &[Record]) -> Table {
+        match self {
+            View::Disks => disks(records),
+            View::Packages => {
+                let mut rows = Vec::new();
+                let mut last = None;
+                for r in records {
+                    let Some(count) = r.packages.filter(|&c| Some(c) != last) else {
+                        continue;
+                    };
+                    let change = match last {
+                        Some(last) => format!("{:+}", count as i64 - last as i64),
+                        None => String::new(),
+                    };
+                    rows.push(vec![date(r.time), count.to_string(), change]);
+                    last = Some(count);
+                }
+                Table {
+                    headers: vec!["DATE", "PACKAGES", "CHANGE"],
+                    rows,
+                }
+            }
+            View::Kernels => {
+                let mut rows = Vec::new();
+                let mut last: Option<&str> = None;
+                for r in records {
+                    let Some(kernel) = r.kernel.as_deref().filter(|&k| Some(k) != last) else {
+                        continue;
+                    };
+                    rows.push(vec![date(r.time), kernel.to_string()]);
+                    last = Some(kernel);
+                }
+                Table {
+                    headers: vec!["FIRST SEEN", "KERNEL"],
+                    rows,
+                }
+            }
+            View::Uptimes => {
+                // The last record of a boot has its longest uptime
+                let mut boots: BTreeMap<i64, &Record> = BTreeMap::new();
+                for r in records.iter().filter(|r| r.uptime.is_some()) {
+                    if let Some(boot_time) = r.boot_time {
+                        boots.insert(boot_time, r);
+                    }
+                }
+                let mut boots: Vec<_> = boots.into_iter().collect();
+                boots.sort_by_key(|(_, r)| std::cmp::Reverse(r.uptime.map(|u| u.0)));
+                let rows = boots
+                    .into_iter()
+                    .take(TOP_UPTIMES)
+                    .enumerate()
+                    .map(|(i, (boot_time, r))| {
+                        vec![
+                            (i + 1).to_string(),
+                            r.uptime.map(|u| u.to_string()).unwrap_or_default(),
+                            date(boot_time),
+                            r.kernel.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                Table {
+                    headers: vec!["RANK", "UPTIME", "BOOTED", "KERNEL"],
+                    rows,
+                }
+            }
+        }
+    }
+}
+
+fn disks(records: &[Record]) -> Table {
+    // Per mount point: the first and the latest record with it
+    let mut mounts: BTreeMap<&str, (i64, &Disk, i64, &Disk)> = BTreeMap::new();
+    for r in records {
+        for disk in &r.disks {
+            mounts
+                .entry(disk.name.as_str())
+                .and_modify(|m| (m.2, m.3) = (r.time, disk))
+                .or_insert((r.time, disk, r.time, disk));
+        }
+    }
+    let units = crate::units::current();
+    let rows = mounts
+        .into_iter()
+        .map(|(name, (since, first, latest_time, latest))| {
+            let change = latest.used as i64 - first.used as i64;
+            let days = (latest_time - since) as f64 / 86400.0;
+            let per_day = match days >= 1.0 {
+                true => growth((change as f64 / days) as i64),
+                false => String::new(),
+            };
+            vec![
+                name.to_string(),
+                units.bytes(first.used as f64),
+                format!(
+                    "{} / {}",
+                    units.bytes(latest.used as f64),
+                    units.bytes(latest.total as f64)
+                ),
+                growth(change),
+                per_day,
+                date(since),
+            ]
+        })
+        .collect();
+    Table {
+        headers: vec!["MOUNT", "FIRST", "LATEST", "GROWTH", "PER DAY", "SINCE"],
+        rows,
+    }
+}
+
+impl Table {
+    /// Replace identifying data, such as a user name in a mount point
+    pub fn scrub(&mut self, scrubber: &Scrubber) {
+        for cell in self.rows.iter_mut().flatten() {
+            *cell = scrubber.text(cell);
+        }
+    }
+
+    /// Aligned table, or in a structured format the rows as objects keyed by
+    /// header in snake case
+    pub fn render(&self, format: Format) -> Result<String> {
+        if format != Format::Text {
+            let rows: Vec<serde_json::Map<String, serde_json::Value>> = self
+                .rows
+                .iter()
+                .map(|row| {
+                    let cells = self.headers.iter().zip(row);
+                    cells
+                        .map(|(h, c)| (h.to_lowercase().replace(' ', "_"), c.clone().into()))
+                        .collect()
+                })
+                .collect();
+            return serialize_list("rows", &rows, format);
+        }
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|i| {
+                self.rows
+                    .iter()
+                    .map(|r| crate::utils::width(&r[i]))
+                    .chain([self.headers[i].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let pad = |s: &str, w: usize| format!("{s}{}", " ".repeat(w - crate::utils::width(s)));
+        let line = |cells: Vec<String>| cells.join("  ").trim_end().to_string();
+
+        let header = self.headers.iter().zip(&widths).map(|(h, w)| pad(h, *w));
+        let mut lines = vec![format!("{GREEN}{BOLD}{}{RESET}", line(header.collect()))];
+        for row in &self.rows {
+            lines.push(line(
+                row.iter().zip(&widths).map(|(c, w)| pad(c, *w)).collect(),
+            ));
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn record(time: i64, boot_time: i64, kernel: &str, packages: usize, used: u64) -> Record {
+        Record {
+            time,
+            boot_time: Some(boot_time),
+            kernel: Some(kernel.into()),
+            uptime: Some(Time((time - boot_time) as u64)),
+            packages: Some(packages),
+            disks: vec![Disk {
+                name: "/".into(),
+                total: 100 << 30,
+                used,
+            }],
+        }
+    }
+
+    fn history() -> Vec<Record> {
+        let day = 86400;
+        let lines = [
+            record(0, -3600, "6.1.0", 1200, 10 << 30),
+            record(day, -3600, "6.1.0", 1200, 11 << 30),
+            record(2 * day, 2 * day - 60, "6.8.0", 1215, 12 << 30),
+        ]
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap())
+        .collect::<Vec<_>>();
+        // Broken lines, e.g. from an interrupted write, are skipped
+        parse(&format!("{}\n{{\"time\":\n", lines.join("\n")))
+    }
+
+    #[test]
+    fn test_views() {
+        let records = history();
+        assert_eq!(records.len(), 3);
+
+        let disks = View::Disks.table(&records);
+        assert_eq!(disks.rows[0][0], "/");
+        assert_eq!(disks.rows[0][3], "+2 GiB");
+        assert_eq!(disks.rows[0][4], "+1 GiB");
+
+        let packages = View::Packages.table(&records);
+        assert_eq!(packages.rows.len(), 2);
+        assert_eq!(packages.rows[1][2], "+15");
+
+        let kernels = View::Kernels.table(&records);
+        let kernels: Vec<_> = kernels.rows.iter().map(|r| r[1].as_str()).collect();
+        assert_eq!(kernels, ["6.1.0", "6.8.0"]);
+
+        let uptimes = View::Uptimes.table(&records);
+        assert_eq!(uptimes.rows.len(), 2);
+        assert_eq!(uptimes.rows[0][1], Time(86400 + 3600).to_string());
+
+        let text = uptimes.render(Format::Text).unwrap();
+        assert_eq!(text.lines().count(), 3);
+        let toml = uptimes.render(Format::Toml).unwrap();
+        assert!(toml.starts_with("[[rows]]\nrank = \"1\"\n"));
+    }
+
+    #[test]
+    fn test_scrub() {
+        let mut records = history();
+        records[2].disks[0].name = "/media/alice/backup".into();
+        let mut disks = View::Disks.table(&records);
+        let mut neofetch = Neofetch::empty();
+        neofetch.user = Ok("alice".into());
+        let redact = crate::redact::Redact {
+            enabled: true,
+            ..Default::default()
+        };
+        disks.scrub(&redact.scrubber(&neofetch));
+        assert!(disks.rows.iter().any(|row| row[0] == "/media/user/backup"));
+    }
+
+    #[test]
+    fn test_compact() {
+        let content = "1\n2\n3\n";
+        assert_eq!(compact(content, 4), None);
+        assert_eq!(compact(content, 3).as_deref(), Some("2\n3\n"));
+        assert_eq!(compact(content, 1).as_deref(), Some(""));
+        assert_eq!(compact("", 1), None);
+    }
+
+    #[test]
+    fn test_history_config() {
+        assert!(Config::default().history.record);
+        let config = Config::parse("history.record = false").unwrap();
+        assert!(!config.history.record);
+        assert!(!config.history.due());
+        let config = Config::parse("history = { interval = '10m', max_records = 500 }");
+        let history = config.unwrap().history;
+        assert_eq!(history.interval, Some(Duration::from_secs(600)));
+        assert_eq!(history.max_records, 500);
+    }
+}
@@ -76,9 +76,11 @@ pub async fn get_host() -> crate::error::Result<String> {
 
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
 pub async fn get_host() -> crate::error::Result<String> {
+    use crate::utils::read_file_to_string;
+
     // Try sys_vendor + product_name (best combo)
-    let vendor = tokio::fs::read_to_string("/sys/devices/virtual/dmi/id/sys_vendor").await;
-    let product = tokio::fs::read_to_string("/sys/devices/virtual/dmi/id/product_name").await;
+    let vendor = read_file_to_string("/sys/devices/virtual/dmi/id/sys_vendor").await;
+    let product = read_file_to_string("/sys/devices/virtual/dmi/id/product_name").await;
 
     if let (Ok(v), Ok(p)) = (&vendor, &product)
         && !v.trim().is_empty()
@@ -101,8 +103,8 @@ pub async fn get_host() -> crate::error::Result<String> {
     }
 
     // Fallback: board_name + product_version
-    let board = tokio::fs::read_to_string("/sys/devices/virtual/dmi/id/board_name").await;
-    let version = tokio::fs::read_to_string("/sys/devices/virtual/dmi/id/product_version").await;
+    let board = read_file_to_string("/sys/devices/virtual/dmi/id/board_name").await;
+    let version = read_file_to_string("/sys/devices/virtual/dmi/id/product_version").await;
 
     match (&board, &version) {
         (Ok(b), Ok(v)) if !b.trim().is_empty() && !v.trim().is_empty() => {
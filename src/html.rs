@@ -0,0 +1,216 @@
+//! HTML rendering
+//!
+//! Converts the colored terminal output into an HTML page, mapping SGR color
+//! codes to inline styles. The logo and info lines are laid out side by side
+//! with CSS instead of the cursor movements used by [`crate::join`].
+
+use crate::Neofetch;
+
+/// xterm colors for SGR 30-37 / 90-97
+const PALETTE: [&str; 16] = [
+    "#000000", "#cd0000", "#00cd00", "#cdcd00", "#0000ee", "#cd00cd", "#00cdcd", "#e5e5e5",
+    "#7f7f7f", "#ff0000", "#00ff00", "#ffff00", "#5c5cff", "#ff00ff", "#00ffff", "#ffffff",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Style {
+    fg: Option<String>,
+    bg: Option<String>,
+    bold: bool,
+}
+
+impl Style {
+    fn css(&self) -> String {
+        let mut css = Vec::new();
+        if let Some(fg) = &self.fg {
+            css.push(format!("color:{fg}"));
+        }
+        if let Some(bg) = &self.bg {
+            css.push(format!("background:{bg}"));
+        }
+        if self.bold {
+            css.push("font-weight:bold".to_string());
+        }
+        css.join(";")
+    }
+
+    /// Apply the parameters of one `ESC [ ... m` sequence
+    fn apply(&mut self, params: &str) {
+        let codes: Vec<u32> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+        let mut iter = codes.iter().copied();
+        while let Some(code) = iter.next() {
+            match code {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                22 => self.bold = false,
+                30..=37 => self.fg = Some(PALETTE[code as usize - 30].to_string()),
+                90..=97 => self.fg = Some(PALETTE[code as usize - 90 + 8].to_string()),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(PALETTE[code as usize - 40].to_string()),
+                100..=107 => self.bg = Some(PALETTE[code as usize - 100 + 8].to_string()),
+                49 => self.bg = None,
+                38 | 48 => {
+                    let color = extended_color(&mut iter);
+                    if code == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Parse the rest of a `38;5;n` or `38;2;r;g;b` sequence
+fn extended_color(iter: &mut impl Iterator<Item = u32>) -> Option<String> {
+    match iter.next()? {
+        5 => {
+            let n = iter.next()?.min(255) as usize;
+            Some(match n {
+                0..=15 => PALETTE[n].to_string(),
+                16..=231 => {
+                    let level = |v: usize| if v == 0 { 0 } else { 55 + v * 40 };
+                    let n = n - 16;
+                    format!(
+                        "#{:02x}{:02x}{:02x}",
+                        level(n / 36),
+                        level(n / 6 % 6),
+                        level(n % 6)
+                    )
+                }
+                _ => {
+                    let v = 8 + (n - 232) * 10;
+                    format!("#{v:02x}{v:02x}{v:02x}")
+                }
+            })
+        }
+        2 => {
+            let (r, g, b) = (iter.next()?, iter.next()?, iter.next()?);
+            Some(format!(
+                "#{:02x}{:02x}{:02x}",
+                r.min(255),
+                g.min(255),
+                b.min(255)
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Escape text for use in HTML content and attributes
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Convert ANSI colored text to HTML spans; other escape sequences are dropped
+pub fn ansi_to_html(s: &str) -> String {
+    let mut out = String::new();
+    let mut style = Style::default();
+    let mut open = Style::default();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            if chars.next_if_eq(&'[').is_some() {
+                let mut params = String::new();
+                while let Some(&p) = chars.peek() {
+                    chars.next();
+                    if p.is_ascii_alphabetic() {
+                        if p == 'm' {
+                            style.apply(&params);
+                        }
+                        break;
+                    }
+                    params.push(p);
+                }
+            }
+            continue;
+        }
+
+        if style != open && c != '\n' {
+            if open != Style::default() {
+                out.push_str("</span>");
+            }
+            if style != Style::default() {
+                out.push_str(&format!("<span style=\"{}\">", style.css()));
+            }
+            open = style.clone();
+        }
+        out.push_str(&escape(&c.to_string()));
+    }
+    if open != Style::default() {
+        out.push_str("</span>");
+    }
+    out
+}
+
+/// Full HTML page with the logo next to the info lines
+pub fn page(neofetch: &Neofetch) -> String {
+    let user = neofetch
+        .user
+        .as_ref()
+        .map(String::as_str)
+        .unwrap_or_default();
+    let hostname = neofetch
+        .hostname
+        .as_ref()
+        .map(String::as_str)
+        .unwrap_or_default();
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ background: #1e1e1e; color: #e5e5e5; font-family: monospace; }}
+.neofetch {{ display: flex; gap: 3ch; }}
+pre {{ margin: 0; }}
+</style>
+</head>
+<body>
+<div class="neofetch">
+<pre>{logo}</pre>
+<pre>{info}</pre>
+</div>
+</body>
+</html>
+"#,
+        title = escape(&format!("{user}@{hostname}")),
+        logo = ansi_to_html(&neofetch.logo()),
+        info = ansi_to_html(&neofetch.info()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{BOLD, GREEN, RED_BG, RESET};
+
+    #[test]
+    fn test_ansi_to_html() {
+        let html = ansi_to_html(&format!("{GREEN}{BOLD}OS: {RESET}a<b\n{RED_BG}   {RESET}"));
+        assert_eq!(
+            html,
+            "<span style=\"color:#00cd00;font-weight:bold\">OS: </span>a&lt;b\n\
+             <span style=\"background:#cd0000\">   </span>"
+        );
+        assert_eq!(ansi_to_html("\x1b[3Aplain"), "plain");
+        assert_eq!(
+            ansi_to_html("\x1b[38;5;196mx"),
+            "<span style=\"color:#ff0000\">x</span>"
+        );
+    }
+}
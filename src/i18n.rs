@@ -0,0 +1,203 @@
+//! Translations of info labels, time units and decimal numbers
+//!
+//! The catalogs are bundled; the language is picked from the detected locale
+//! (`de_DE.UTF-8` selects German) or set explicitly with [`set`]. Labels
+//! without a translation, such as those of custom modules, are shown as-is.
+
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use serde::Deserialize;
+
+/// Language of the displayed labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+    Zh,
+}
+
+/// Plural category of a count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plural {
+    One,
+    Other,
+}
+
+/// Label translations, keyed by the English label
+const LABELS: &[(&str, &str, &str)] = &[
+    // (en, de, zh)
+    ("OS", "Betriebssystem", "操作系统"),
+    ("User", "Benutzer", "用户"),
+    ("Host", "Host", "主机"),
+    ("Hostname", "Hostname", "主机名"),
+    ("Rom", "ROM", "ROM"),
+    ("Baseband", "Baseband", "基带"),
+    ("Kernel", "Kernel", "内核"),
+    ("Uptime", "Laufzeit", "运行时间"),
+    ("Load", "Last", "负载"),
+    ("Packages", "Pakete", "软件包"),
+    ("Shell", "Shell", "Shell"),
+    ("Display", "Bildschirm", "显示器"),
+    ("DE", "DE", "桌面环境"),
+    ("WM", "WM", "窗口管理器"),
+    ("Theme", "Design", "主题"),
+    ("WM Theme", "WM-Design", "窗口主题"),
+    ("Terminal", "Terminal", "终端"),
+    ("Disk", "Datenträger", "磁盘"),
+    ("CPU", "CPU", "处理器"),
+    ("GPU", "GPU", "显卡"),
+    ("Memory", "Arbeitsspeicher", "内存"),
+    ("Battery", "Akku", "电池"),
+    ("Locale", "Gebietsschema", "区域设置"),
+    ("IP", "IP", "IP"),
+    ("Local IP", "Lokale IP", "本地 IP"),
+    ("Temperature", "Temperatur", "温度"),
+    ("Network", "Netzwerk", "网络"),
+];
+
+/// Time units as (key, en one, en other, de one, de other, zh)
+const UNITS: &[(&str, &str, &str, &str, &str, &str)] = &[
+    ("sec", "sec", "secs", "Sekunde", "Sekunden", "秒"),
+    ("min", "min", "mins", "Minute", "Minuten", "分钟"),
+    ("hour", "hour", "hours", "Stunde", "Stunden", "小时"),
+    ("day", "day", "days", "Tag", "Tage", "天"),
+];
+
+static LANGUAGE: RwLock<Language> = RwLock::new(Language::En);
+
+/// Language of the displayed labels
+pub fn current() -> Language {
+    LANGUAGE.read().map(|l| *l).unwrap_or_default()
+}
+
+/// Change the language of the displayed labels
+pub fn set(language: Language) {
+    if let Ok(mut current) = LANGUAGE.write() {
+        *current = language;
+    }
+}
+
+/// `label` in the current language
+pub fn tr(label: &str) -> String {
+    current().label(label).to_string()
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    /// Parse a language code or a locale such as `zh_CN.UTF-8` or `de-AT`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Ok(Language::En),
+            "de" => Ok(Language::De),
+            "zh" => Ok(Language::Zh),
+            _ => Err(format!("unsupported language '{s}' (en, de, zh)")),
+        }
+    }
+}
+
+impl TryFrom<String> for Language {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Language {
+    /// Language of a detected locale, English when there is no catalog for it
+    pub fn from_locale(locale: &str) -> Language {
+        locale.parse().unwrap_or_default()
+    }
+
+    /// Translation of an English label
+    pub fn label(self, label: &str) -> &str {
+        let Some(&(_, de, zh)) = LABELS.iter().find(|(en, _, _)| *en == label) else {
+            return label;
+        };
+        match self {
+            Language::En => label,
+            Language::De => de,
+            Language::Zh => zh,
+        }
+    }
+
+    /// Plural category of `n`
+    pub fn plural(self, n: u64) -> Plural {
+        match self {
+            // English keeps the singular for 0 ("0 min"), as it was shown before
+            Language::En if n <= 1 => Plural::One,
+            Language::De if n == 1 => Plural::One,
+            _ => Plural::Other,
+        }
+    }
+
+    /// `n` with a time unit (`sec`, `min`, `hour` or `day`) in the right plural form
+    pub fn unit(self, n: u64, unit: &str) -> String {
+        let Some(&(_, en1, en, de1, de, zh)) = UNITS.iter().find(|u| u.0 == unit) else {
+            return format!("{n} {unit}");
+        };
+        let one = self.plural(n) == Plural::One;
+        match self {
+            Language::En => format!("{n} {}", if one { en1 } else { en }),
+            Language::De => format!("{n} {}", if one { de1 } else { de }),
+            Language::Zh => format!("{n}{zh}"),
+        }
+    }
+
+    /// Separator between the parts of a duration
+    pub fn separator(self) -> &'static str {
+        match self {
+            Language::En | Language::De => ", ",
+            Language::Zh => " ",
+        }
+    }
+
+    /// A number formatted with `.` as the decimal separator, localized
+    pub fn decimal(self, number: String) -> String {
+        match self {
+            Language::De => number.replace('.', ","),
+            Language::En | Language::Zh => number,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "cli")]
+    use crate::config::Config;
+
+    #[test]
+    fn test_language_from_locale() {
+        assert_eq!(Language::from_locale("zh_CN.UTF-8"), Language::Zh);
+        assert_eq!(Language::from_locale("de-AT"), Language::De);
+        assert_eq!(Language::from_locale("fr_FR.UTF-8"), Language::En);
+        assert!("fr".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_catalogs() {
+        assert_eq!(Language::De.label("Memory"), "Arbeitsspeicher");
+        assert_eq!(Language::Zh.label("Uptime"), "运行时间");
+        assert_eq!(Language::De.label("Build"), "Build");
+
+        assert_eq!(Language::En.unit(1, "day"), "1 day");
+        assert_eq!(Language::En.unit(0, "min"), "0 min");
+        assert_eq!(Language::De.unit(2, "hour"), "2 Stunden");
+        assert_eq!(Language::Zh.unit(3, "day"), "3天");
+        assert_eq!(Language::De.decimal("5.6 GiB".into()), "5,6 GiB");
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_language_config() {
+        let language = Config::parse("language = 'zh_CN'").unwrap().language;
+        assert_eq!(language, Some(Language::Zh));
+        assert!(Config::parse("language = 'tlh'").is_err());
+    }
+}
@@ -1,6 +1,10 @@
 use crate::error::{NeofetchError, Result};
 use public_ip_address::perform_lookup;
 use std::net::UdpSocket;
+use std::time::Duration;
+
+/// Longest wait for the public IP lookup, which needs the network
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub fn get_local_ip() -> Result<String> {
     let socket = UdpSocket::bind("0.0.0.0:0")
@@ -15,9 +19,12 @@ pub fn get_local_ip() -> Result<String> {
 }
 
 pub async fn get_ip() -> Result<String> {
-    let response = perform_lookup(None)
+    let response = tokio::time::timeout(LOOKUP_TIMEOUT, perform_lookup(None))
         .await
-        .map_err(|e| NeofetchError::system_call(format!("Failed to perform public IP lookup: {}", e)))?;
+        .map_err(|_| NeofetchError::timeout(format!("public IP lookup after {LOOKUP_TIMEOUT:?}")))?
+        .map_err(|e| {
+            NeofetchError::system_call(format!("Failed to perform public IP lookup: {}", e))
+        })?;
 
     let ip = response.ip;
     let s = match (response.country_code, response.city) {
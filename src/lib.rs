@@ -1,9 +1,11 @@
 // Core modules (always available)
 pub mod color;
 pub mod error;
+pub mod i18n;
 pub mod mappings;
 pub mod platform;
 pub mod share;
+pub mod units;
 pub mod utils;
 
 // icon module depends on os::Distro
@@ -66,18 +68,42 @@ use crate::color::{
     MAGENTA_BG, RED, RED_BG, RESET, WHITE_BG, YELLOW_BG, cursor_down, cursor_forward, cursor_up,
 };
 
+/// Like [`join`], but pads every line instead of moving the cursor, so the
+/// result has exactly one line per row and can be redrawn in place
+pub fn join_padded(left: &str, right: &str) -> String {
+    let left_max_w = left.lines().map(crate::utils::width).max().unwrap_or(0);
+    let gap = 3;
+    let mut left_lines = left.lines();
+    let mut right_lines = right.lines();
+    let mut s = String::new();
+    loop {
+        let (l, r) = (left_lines.next(), right_lines.next());
+        if l.is_none() && r.is_none() {
+            break;
+        }
+        let l = l.unwrap_or_default();
+        s.push_str(l);
+        if let Some(r) = r {
+            s.push_str(&" ".repeat(left_max_w + gap - crate::utils::width(l)));
+            s.push_str(r);
+        }
+        s.push('\n');
+    }
+    s
+}
+
 pub fn join(left: String, right: String) -> String {
     let mut s = String::new();
     let left_h = left.lines().count();
     let right_h = right.lines().count();
     let max_h = left_h.max(right_h);
-    let left_max_w = left.lines().map(ansi_width::ansi_width).max().unwrap_or(0);
+    let left_max_w = left.lines().map(crate::utils::width).max().unwrap_or(0);
 
     let gap = 3;
 
     for i in left.lines() {
         s.push_str(i);
-        let n = left_max_w + gap - ansi_width::ansi_width(i);
+        let n = left_max_w + gap - crate::utils::width(i);
         s.push_str(&" ".repeat(n));
         s.push('\n');
     }
@@ -107,26 +133,37 @@ mod cli {
     use cpu::Cpu;
     use disk::Disk;
     use display::{Display, get_display};
+    use futures::Stream;
+    use futures::stream::FuturesUnordered;
     use gpu::Gpu;
     use hostname::get_hostname;
+    use memory::Memory;
     use os::OS;
     use packages::Packages;
+    use serde::{Deserialize, Serialize};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use system::LoadAverage;
+    use tokio::sync::OnceCell;
     use uptime::Time;
-    use which_shell::ShellVersion;
-    use which_terminal::TerminalInfo;
 
     use crate::battery::get_battery;
+    use crate::collector::Extra;
     use crate::cpu::get_cpu;
     use crate::de::get_de;
     use crate::disk::get_disk;
+    use crate::error::result_serde;
     use crate::host::get_host;
     use crate::host::{get_baseband, get_rom};
+    use crate::i18n::tr;
     use crate::ip::get_ip;
     use crate::kernel::get_kernel;
     use crate::locale::get_locale;
     use crate::memory::get_memory;
+    use crate::module::Module;
     use crate::packages::get_packages;
     use crate::shell::which_shell;
+    use crate::system::get_load_average;
     use crate::terminal::get_terminal;
     use crate::uptime::get_uptime;
     use crate::user::get_user;
@@ -135,308 +172,461 @@ mod cli {
     use crate::{network::get_network_info, temperature::get_temperature_sensors};
 
     /// System information container
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Neofetch {
+        #[serde(with = "result_serde")]
         pub os: Result<OS>,
+        #[serde(with = "result_serde")]
         pub user: Result<String>,
+        #[serde(with = "result_serde")]
         pub host: Result<String>,
+        #[serde(with = "result_serde")]
         pub hostname: Result<String>,
+        #[serde(with = "result_serde")]
         pub rom: Result<String>,
+        #[serde(with = "result_serde")]
         pub baseband: Result<String>,
+        #[serde(with = "result_serde")]
         pub kernel: Result<String>,
+        #[serde(rename = "uptime_seconds", with = "result_serde")]
         pub uptime: Result<Time>,
+        #[serde(default = "result_serde::missing", with = "result_serde")]
+        pub load_average: Result<LoadAverage>,
+        #[serde(with = "result_serde")]
         pub packages: Result<Packages>,
-        pub shell: Result<ShellVersion>,
+        #[serde(with = "result_serde")]
+        pub shell: Result<String>,
+        #[serde(with = "result_serde")]
         pub display: Result<Vec<Display>>,
+        #[serde(with = "result_serde")]
         pub de: Result<String>,
+        #[serde(with = "result_serde")]
         pub wm: Result<String>,
+        #[serde(with = "result_serde")]
         pub wm_theme: Result<String>,
-        pub terminal: Result<TerminalInfo>,
+        #[serde(with = "result_serde")]
+        pub terminal: Result<String>,
+        #[serde(with = "result_serde")]
         pub disk: Result<Vec<Disk>>,
+        #[serde(with = "result_serde")]
         pub cpu: Result<Cpu>,
+        #[serde(with = "result_serde")]
         pub gpu: Result<Vec<Gpu>>,
-        pub memory: Result<String>,
+        #[serde(with = "result_serde")]
+        pub memory: Result<Memory>,
+        #[serde(rename = "battery_percent", with = "result_serde")]
         pub battery: Result<u32>,
+        #[serde(with = "result_serde")]
         pub locale: Result<String>,
+        #[serde(with = "result_serde")]
         pub ip: Result<String>,
+        #[serde(with = "result_serde")]
         pub local_ip: Result<String>,
+        #[serde(with = "result_serde")]
         pub temperature: Result<Vec<temperature::TempSensor>>,
+        #[serde(with = "result_serde")]
         pub network: Result<Vec<network::NetworkInfo>>,
+        /// Values of registered [`Collector`](crate::collector::Collector)s
+        #[serde(flatten)]
+        pub extra: Extra,
     }
 
-    impl Neofetch {
-        /// Collect all system information
-        pub async fn new() -> Neofetch {
-            let (
-                shell,
-                os,
-                user,
-                host,
-                rom,
-                baseband,
-                kernel,
-                uptime,
-                packages,
-                display,
-                wm,
-                wm_theme,
-                terminal,
-                disk,
-                cpu,
-                gpu,
-                memory,
-                battery,
-                hostname,
-                locale,
-                temperature,
-                network,
-                ip,
-            ) = tokio::join!(
-                which_shell(),
-                get_os(),
-                get_user(),
-                get_host(),
-                get_rom(),
-                get_baseband(),
-                get_kernel(),
-                get_uptime(),
-                get_packages(),
-                get_display(),
-                get_wm(),
-                get_wm_theme(),
-                get_terminal(),
-                get_disk(),
-                get_cpu(),
-                get_gpu(),
-                get_memory(),
-                get_battery(),
-                get_hostname(),
-                get_locale(),
-                get_temperature_sensors(),
-                get_network_info(),
-                get_ip(),
-            );
-
-            // Get desktop environment based on OS
-            let de = match &os {
-                Ok(o) => get_de(o.clone()),
-                Err(e) => Err(NeofetchError::data_unavailable(format!(
-                    "Cannot detect DE without OS: {}",
-                    e
-                ))),
-            };
-            let local_ip = ip::get_local_ip();
+    /// Stores one collected result into its field
+    type Update = Box<dyn FnOnce(&mut Neofetch) + Send>;
 
-            Neofetch {
-                os,
-                user,
-                host,
-                rom,
-                baseband,
-                kernel,
-                uptime,
-                packages,
-                shell,
-                display,
-                de,
-                wm,
-                wm_theme,
-                terminal,
-                disk,
-                cpu,
-                gpu,
-                memory,
-                battery,
-                hostname,
-                locale,
-                ip,
-                local_ip,
-                temperature,
-                network,
+    fn update<T: Send + 'static>(
+        result: Result<T>,
+        field: fn(&mut Neofetch) -> &mut Result<T>,
+    ) -> Update {
+        Box::new(move |n| *field(n) = result)
+    }
+
+    /// Desktop environment for the detected OS
+    fn de_for(os: &Result<OS>) -> Result<String> {
+        match os {
+            Ok(o) => get_de(o.clone()),
+            Err(e) => Err(NeofetchError::data_unavailable(format!(
+                "Cannot detect DE without OS: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Run the collector for a single module
+    async fn collect(module: Module) -> Update {
+        match module {
+            Module::Os => update(get_os().await, |n| &mut n.os),
+            Module::User => update(get_user().await, |n| &mut n.user),
+            Module::Host => update(get_host().await, |n| &mut n.host),
+            Module::Hostname => update(get_hostname().await, |n| &mut n.hostname),
+            Module::Rom => update(get_rom().await, |n| &mut n.rom),
+            Module::Baseband => update(get_baseband().await, |n| &mut n.baseband),
+            Module::Kernel => update(get_kernel().await, |n| &mut n.kernel),
+            Module::Uptime => update(get_uptime().await, |n| &mut n.uptime),
+            Module::LoadAverage => update(get_load_average().await, |n| &mut n.load_average),
+            Module::Packages => update(get_packages().await, |n| &mut n.packages),
+            Module::Shell => update(which_shell().await.map(|s| s.to_string()), |n| &mut n.shell),
+            Module::Display => update(get_display().await, |n| &mut n.display),
+            Module::De => update(de_for(&get_os().await), |n| &mut n.de),
+            Module::Wm => update(get_wm().await, |n| &mut n.wm),
+            Module::WmTheme => update(get_wm_theme().await, |n| &mut n.wm_theme),
+            Module::Terminal => update(get_terminal().await.map(|t| t.to_string()), |n| {
+                &mut n.terminal
+            }),
+            Module::Disk => update(get_disk().await, |n| &mut n.disk),
+            Module::Cpu => update(get_cpu().await, |n| &mut n.cpu),
+            Module::Gpu => update(get_gpu().await, |n| &mut n.gpu),
+            Module::Memory => update(get_memory().await, |n| &mut n.memory),
+            Module::Battery => update(get_battery().await, |n| &mut n.battery),
+            Module::Locale => update(get_locale().await, |n| &mut n.locale),
+            // A public IP hidden by redaction is not looked up at all
+            Module::Ip if crate::config::current().redact.skips(Module::Ip) => {
+                update(Err(NeofetchError::data_unavailable("redacted")), |n| {
+                    &mut n.ip
+                })
+            }
+            Module::Ip => update(get_ip().await, |n| &mut n.ip),
+            Module::LocalIp => update(ip::get_local_ip(), |n| &mut n.local_ip),
+            Module::Temperature => update(get_temperature_sensors().await, |n| &mut n.temperature),
+            Module::Network => update(get_network_info().await, |n| &mut n.network),
+            Module::Extra(name) => {
+                let value = crate::collector::collect(name).await;
+                Box::new(move |n| n.extra.set(name, value))
             }
         }
     }
 
-    impl std::fmt::Display for Neofetch {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            let mut info = String::new();
-            let mut icon = String::new();
-            let user = self.user.as_ref().ok().cloned().unwrap_or_default();
-            let hostname = self.hostname.as_ref().ok().cloned().unwrap_or_default();
+    /// `default` text of a module, or its configured template filled from `value`
+    fn render_text(module: Module, value: serde_json::Value, default: String) -> String {
+        match crate::config::current().template(module) {
+            Some(template) => template.render(&crate::template::context(module, value, default)),
+            None => default,
+        }
+    }
 
-            info.push_str(&format!(
-                "{RESET}{RED}{BOLD}{user}{RESET}@{RED}{BOLD}{hostname}{RESET}\n"
-            ));
-            info.push_str("-------\n");
+    /// Text of one item of `module`: its template when configured, else its `Display` text
+    fn text<T: std::fmt::Display + Serialize>(module: Module, item: &T) -> String {
+        let value = serde_json::to_value(item).unwrap_or_default();
+        render_text(module, value, item.to_string())
+    }
 
-            // Helper: append a labeled field if Ok
-            fn append<T: std::fmt::Display>(
-                info: &mut String,
-                label: &str,
-                result: &crate::error::Result<T>,
-            ) {
-                if let Ok(value) = result {
-                    info.push_str(&format!("{GREEN}{BOLD}{label}: {RESET}{value}\n"));
-                }
-            }
+    impl Neofetch {
+        /// Collect all system information
+        pub async fn new() -> Neofetch {
+            let mut neofetch = Neofetch::empty();
+            neofetch.refresh(&Module::all()).await;
+            neofetch
+        }
 
-            // OS
-            if let Ok(os) = &self.os {
-                icon = os.distro.icon();
-                append(&mut info, "OS", &self.os);
+        /// Instance with every module marked as not collected yet
+        pub fn empty() -> Neofetch {
+            fn pending<T>() -> Result<T> {
+                Err(NeofetchError::data_unavailable("not collected"))
             }
 
-            append(&mut info, "Host", &self.host);
-            append(&mut info, "Rom", &self.rom);
-            append(&mut info, "Baseband", &self.baseband);
-            append(&mut info, "Kernel", &self.kernel);
-
-            if let Ok(uptime) = &self.uptime
-                && uptime.0 > 0
-            {
-                append(&mut info, "Uptime", &self.uptime);
+            Neofetch {
+                os: pending(),
+                user: pending(),
+                host: pending(),
+                hostname: pending(),
+                rom: pending(),
+                baseband: pending(),
+                kernel: pending(),
+                uptime: pending(),
+                load_average: pending(),
+                packages: pending(),
+                shell: pending(),
+                display: pending(),
+                de: pending(),
+                wm: pending(),
+                wm_theme: pending(),
+                terminal: pending(),
+                disk: pending(),
+                cpu: pending(),
+                gpu: pending(),
+                memory: pending(),
+                battery: pending(),
+                locale: pending(),
+                ip: pending(),
+                local_ip: pending(),
+                temperature: pending(),
+                network: pending(),
+                extra: Extra::pending(),
             }
+        }
 
-            if let Ok(packages) = &self.packages {
-                let s = packages.to_string();
-                if !s.trim().is_empty() {
-                    info.push_str(&format!("{GREEN}{BOLD}Packages: {RESET}{s}\n"));
-                }
-            }
+        /// Collect `modules` concurrently, yielding each result as soon as its
+        /// collector finishes, in the shape used by [`Neofetch::value`]
+        ///
+        /// Fast modules arrive first; feed the events to [`Neofetch::set_value`]
+        /// to render a partially collected instance while slow ones are pending.
+        pub fn stream(
+            modules: &[Module],
+        ) -> impl Stream<Item = (Module, Result<serde_json::Value>)> + use<> {
+            // DE is derived from the OS, so both share a single OS detection
+            let os = Arc::new(OnceCell::new());
+            modules
+                .iter()
+                .map(|&module| {
+                    let os = Arc::clone(&os);
+                    async move {
+                        let mut scratch = Neofetch::empty();
+                        match module {
+                            Module::Os => scratch.os = os.get_or_init(get_os).await.clone(),
+                            Module::De => scratch.de = de_for(os.get_or_init(get_os).await),
+                            _ => collect(module).await(&mut scratch),
+                        }
+                        (module, scratch.value(module))
+                    }
+                })
+                .collect::<FuturesUnordered<_>>()
+        }
 
-            append(&mut info, "Shell", &self.shell);
+        /// Re-run the collectors for `modules` concurrently and replace their fields
+        ///
+        /// Use [`Module::volatile`] to update only the values that change at runtime.
+        pub async fn refresh(&mut self, modules: &[Module]) {
+            self.refresh_timed(modules).await;
+        }
 
-            if let Ok(displays) = &self.display {
-                for display in displays {
-                    let key = if let Some(i) = &display.friendly_name {
-                        format!("{GREEN}{BOLD}Display({i})")
-                    } else {
-                        display
-                            .name
-                            .clone()
-                            .map_or(format!("{GREEN}{BOLD}Display"), |s| {
-                                format!("{GREEN}{BOLD}Display({s})")
-                            })
-                    };
-                    info.push_str(&format!("{key}: {RESET}{}\n", display));
-                }
+        /// [`Neofetch::refresh`] returning the wall-clock time of each collector
+        pub(crate) async fn refresh_timed(
+            &mut self,
+            modules: &[Module],
+        ) -> Vec<(Module, Duration)> {
+            // DE is derived from the OS, so reuse the OS result when it is requested
+            // too or was already collected
+            let derive_de =
+                modules.contains(&Module::De) && (modules.contains(&Module::Os) || self.os.is_ok());
+            let updates = futures::future::join_all(
+                modules
+                    .iter()
+                    .filter(|m| !(derive_de && **m == Module::De))
+                    .map(|&m| async move {
+                        let start = Instant::now();
+                        let update = collect(m).await;
+                        (m, start.elapsed(), update)
+                    }),
+            )
+            .await;
+
+            let mut timings = Vec::new();
+            for (module, elapsed, update) in updates {
+                update(self);
+                timings.push((module, elapsed));
             }
+            if derive_de {
+                let start = Instant::now();
+                self.de = de_for(&self.os);
+                timings.push((Module::De, start.elapsed()));
+            }
+            timings
+        }
+    }
 
-            append(&mut info, "DE", &self.de);
-
-            if let Ok(wm) = &self.wm {
-                if let Ok(theme) = &self.wm_theme {
-                    info.push_str(&format!(
-                        "{GREEN}{BOLD}WM: {RESET}{wm} (Theme: {RESET}{theme})\n"
-                    ));
-                } else {
-                    info.push_str(&format!("{GREEN}{BOLD}WM: {RESET}{wm}\n"));
+    impl Neofetch {
+        /// `(label, text)` info lines of one module, none when it has no value
+        ///
+        /// An empty label continues the entry above (e.g. further sensors).
+        pub fn lines(&self, module: Module) -> Vec<(String, String)> {
+            fn one<T: std::fmt::Display + Serialize>(
+                module: Module,
+                label: &str,
+                result: &Result<T>,
+            ) -> Vec<(String, String)> {
+                match result {
+                    Ok(value) => vec![(tr(label), text(module, value))],
+                    Err(_) => Vec::new(),
                 }
             }
 
-            append(&mut info, "Terminal", &self.terminal);
+            /// First entry labeled, the rest as continuation lines
+            fn list(label: &str, items: impl Iterator<Item = String>) -> Vec<(String, String)> {
+                let mut label = tr(label);
+                items.map(|s| (std::mem::take(&mut label), s)).collect()
+            }
 
-            if let Ok(disks) = &self.disk {
-                for disk in disks {
-                    if disk.total > 0 {
-                        info.push_str(&format!(
-                            "{GREEN}{BOLD}Disk({}): {RESET}{}\n",
-                            disk.name, disk
-                        ));
+            match module {
+                Module::Os => one(module, "OS", &self.os),
+                Module::User => one(module, "User", &self.user),
+                Module::Host => one(module, "Host", &self.host),
+                Module::Hostname => one(module, "Hostname", &self.hostname),
+                Module::Rom => one(module, "Rom", &self.rom),
+                Module::Baseband => one(module, "Baseband", &self.baseband),
+                Module::Kernel => one(module, "Kernel", &self.kernel),
+                Module::Uptime => match &self.uptime {
+                    Ok(uptime) if uptime.0 > 0 => one(module, "Uptime", &self.uptime),
+                    _ => Vec::new(),
+                },
+                Module::LoadAverage => one(module, "Load", &self.load_average),
+                Module::Packages => match &self.packages {
+                    Ok(packages) if !packages.to_string().trim().is_empty() => {
+                        one(module, "Packages", &self.packages)
                     }
-                }
+                    _ => Vec::new(),
+                },
+                Module::Shell => one(module, "Shell", &self.shell),
+                Module::Display => match &self.display {
+                    Ok(displays) => displays
+                        .iter()
+                        .map(|display| {
+                            let label = match (&display.friendly_name, &display.name) {
+                                (Some(i), _) | (None, Some(i)) => format!("{}({i})", tr("Display")),
+                                (None, None) => tr("Display"),
+                            };
+                            (label, text(module, display))
+                        })
+                        .collect(),
+                    Err(_) => Vec::new(),
+                },
+                Module::De => one(module, "DE", &self.de),
+                Module::Wm => match &self.wm {
+                    Ok(wm) => {
+                        let theme = self.wm_theme.as_ref().ok();
+                        let default = match theme {
+                            Some(theme) => format!("{wm} ({}: {RESET}{theme})", tr("Theme")),
+                            None => wm.clone(),
+                        };
+                        let context = serde_json::json!({ "name": wm, "theme": theme });
+                        vec![(tr("WM"), render_text(module, context, default))]
+                    }
+                    Err(_) => Vec::new(),
+                },
+                Module::WmTheme => one(module, "WM Theme", &self.wm_theme),
+                Module::Terminal => one(module, "Terminal", &self.terminal),
+                Module::Disk => match &self.disk {
+                    Ok(disks) => disks
+                        .iter()
+                        .filter(|disk| disk.total > 0)
+                        .map(|disk| (format!("{}({})", tr("Disk"), disk.name), text(module, disk)))
+                        .collect(),
+                    Err(_) => Vec::new(),
+                },
+                Module::Cpu => one(module, "CPU", &self.cpu),
+                Module::Gpu => match &self.gpu {
+                    Ok(gpus) => gpus.iter().map(|g| (tr("GPU"), text(module, g))).collect(),
+                    Err(_) => Vec::new(),
+                },
+                Module::Memory => one(module, "Memory", &self.memory),
+                Module::Battery => match &self.battery {
+                    Ok(battery) => {
+                        let default = format!("{battery}%");
+                        vec![(
+                            tr("Battery"),
+                            render_text(module, (*battery).into(), default),
+                        )]
+                    }
+                    Err(_) => Vec::new(),
+                },
+                Module::Locale => one(module, "Locale", &self.locale),
+                Module::Ip => one(module, "IP", &self.ip),
+                Module::LocalIp => one(module, "Local IP", &self.local_ip),
+                Module::Temperature => match &self.temperature {
+                    Ok(sensors) => list(
+                        "Temperature",
+                        sensors.iter().take(3).map(|s| text(module, s)),
+                    ),
+                    Err(_) => Vec::new(),
+                },
+                Module::Network => match &self.network {
+                    Ok(interfaces) => {
+                        let active = interfaces
+                            .iter()
+                            .filter(|iface| iface.is_up && iface.ipv4_address.is_some())
+                            .take(3)
+                            .map(|iface| {
+                                let ip = iface.ipv4_address.as_deref().unwrap_or("N/A");
+                                let default = format!("{} ({ip})", iface.interface_name);
+                                let value = serde_json::to_value(iface).unwrap_or_default();
+                                render_text(module, value, default)
+                            });
+                        list("Network", active)
+                    }
+                    Err(_) => Vec::new(),
+                },
+                Module::Extra(name) => match self.extra.get(name) {
+                    Some(Ok(value)) => crate::collector::line(name, value)
+                        .map(|(label, s)| (label, render_text(module, value.clone(), s)))
+                        .into_iter()
+                        .collect(),
+                    _ => Vec::new(),
+                },
             }
+        }
 
-            append(&mut info, "CPU", &self.cpu);
+        /// Distro logo, empty when the OS could not be detected
+        pub fn logo(&self) -> String {
+            let logo = self
+                .os
+                .as_ref()
+                .map(|os| os.distro.icon())
+                .unwrap_or_default();
+            crate::theme::adapt(&logo)
+        }
 
-            if let Ok(gpu) = &self.gpu {
-                for g in gpu {
-                    info.push_str(&format!("{GREEN}{BOLD}GPU: {RESET}{g}\n"));
+        /// Colored `Label: value` lines shown next to the logo
+        pub fn info(&self) -> String {
+            let mut info = String::new();
+            let user = self.user.as_ref().ok().cloned().unwrap_or_default();
+            let hostname = self.hostname.as_ref().ok().cloned().unwrap_or_default();
+
+            let config = crate::config::current();
+            let style = &config.info;
+
+            let title = style.title(&user, &hostname);
+            if !title.is_empty() {
+                info.push_str(&format!("{RESET}{title}\n"));
+                let underline = style.underline(crate::utils::width(&title));
+                if !underline.is_empty() {
+                    info.push_str(&format!("{underline}\n"));
                 }
             }
 
-            append(&mut info, "Memory", &self.memory);
-
-            if let Ok(sensors) = &self.temperature
-                && !sensors.is_empty()
-            {
-                let k = "Temperature: ";
-                for (i, sensor) in sensors.iter().take(3).enumerate() {
-                    if i == 0 {
-                        info.push_str(&format!("{GREEN}{BOLD}{k}{RESET}{sensor}\n"));
+            let lines: Vec<Vec<_>> = config
+                .layout()
+                .into_iter()
+                .map(|m| {
+                    let lines = self.lines(m).into_iter();
+                    lines.map(|(label, v)| (style.label(m, label), v)).collect()
+                })
+                .collect();
+            let separator = &style.separator;
+            // With alignment every label is padded to the widest one
+            let column = style.align.then(|| {
+                let labels = lines.iter().flatten().map(|(label, _)| label);
+                labels.map(|l| crate::utils::width(l)).max().unwrap_or(0)
+            });
+            for module_lines in lines {
+                let mut indent = 0;
+                for (label, value) in module_lines {
+                    if label.is_empty() {
+                        let pad = " ".repeat(indent);
+                        info.push_str(&format!("{GREEN}{BOLD}{pad}{RESET}{value}\n"));
                     } else {
+                        let width = crate::utils::width(&label);
+                        let pad = " ".repeat(column.unwrap_or(width) - width);
+                        indent = column.unwrap_or(width) + crate::utils::width(separator);
                         info.push_str(&format!(
-                            "{GREEN}{BOLD}{}{RESET}{sensor}\n",
-                            " ".repeat(k.len()),
+                            "{GREEN}{BOLD}{label}{pad}{separator}{RESET}{value}\n"
                         ));
                     }
                 }
             }
 
-            if let Ok(battery) = &self.battery {
-                info.push_str(&format!("{GREEN}{BOLD}Battery: {RESET}{battery}%\n"));
-            }
-
-            append(&mut info, "Local IP", &self.local_ip);
-            append(&mut info, "IP", &self.ip);
-
-            if let Ok(interfaces) = &self.network {
-                let active_interfaces: Vec<_> = interfaces
-                    .iter()
-                    .filter(|iface| iface.is_up && iface.ipv4_address.is_some())
-                    .collect();
-
-                if !active_interfaces.is_empty() {
-                    let k = "Network: ";
-                    for (i, iface) in active_interfaces.iter().take(3).enumerate() {
-                        let ip = iface
-                            .ipv4_address
-                            .as_deref()
-                            .unwrap_or("N/A");
-                        if i == 0 {
-                            info.push_str(&format!(
-                                "{GREEN}{BOLD}{k}{RESET}{} ({ip})\n",
-                                iface.interface_name,
-                            ));
-                        } else {
-                            info.push_str(&format!(
-                                "{GREEN}{BOLD}{}{RESET}{} ({ip})\n",
-                                " ".repeat(k.len()),
-                                iface.interface_name,
-                            ));
-                        }
-                    }
-                }
+            // The palette shows the terminal colors as they are
+            let mut info = crate::theme::adapt(&info);
+            let palette = config.palette.render();
+            if !palette.is_empty() {
+                info.push('\n');
+                info.push_str(&palette);
             }
+            info
+        }
+    }
 
-            append(&mut info, "Locale", &self.locale);
-
-            // Color bars
-            let color_str: String = [
-                BLACK_BG, RED_BG, GREEN_BG, YELLOW_BG, BLUE_BG, MAGENTA_BG, CYAN_BG, WHITE_BG,
-            ]
-            .map(|c| format!("{c}   "))
-            .into_iter()
-            .collect();
-            info.push('\n');
-            info.push_str(&(color_str + RESET + "\n"));
-
-            let color_str: String = [
-                BRIGHT_BLACK_BG,
-                BRIGHT_RED_BG,
-                BRIGHT_GREEN_BG,
-                BRIGHT_YELLOW_BG,
-                BRIGHT_BLUE_BG,
-                BRIGHT_MAGENTA_BG,
-                BRIGHT_CYAN_BG,
-                BRIGHT_WHITE_BG,
-            ]
-            .map(|c| format!("{c}   "))
-            .into_iter()
-            .collect();
-            info.push_str(&(color_str + RESET + "\n"));
-
-            write!(f, "{}", join(icon, info))
+    impl std::fmt::Display for Neofetch {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", join(self.logo(), self.info()))
         }
     }
 }
@@ -444,6 +634,51 @@ mod cli {
 #[cfg(feature = "cli")]
 pub use cli::Neofetch;
 
+#[cfg(feature = "cli")]
+pub mod cache;
+#[cfg(feature = "cli")]
+pub mod check;
+#[cfg(feature = "cli")]
+pub mod collector;
+#[cfg(feature = "cli")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod custom;
+#[cfg(feature = "cli")]
+pub mod diff;
+#[cfg(feature = "cli")]
+pub mod explain;
+#[cfg(feature = "cli")]
+pub mod explore;
+#[cfg(feature = "cli")]
+pub mod glyph;
+#[cfg(feature = "cli")]
+pub mod history;
+#[cfg(feature = "cli")]
+pub mod html;
+#[cfg(feature = "cli")]
+pub mod module;
+#[cfg(feature = "cli")]
+pub mod output;
+#[cfg(feature = "cli")]
+pub mod palette;
+#[cfg(feature = "cli")]
+pub mod redact;
+#[cfg(feature = "cli")]
+pub mod report;
+#[cfg(feature = "cli")]
+pub mod script;
+#[cfg(feature = "cli")]
+pub mod serve;
+#[cfg(feature = "cli")]
+pub mod template;
+#[cfg(feature = "cli")]
+pub mod theme;
+#[cfg(feature = "cli")]
+pub mod timings;
+#[cfg(feature = "cli")]
+pub mod watch;
+
 #[cfg(feature = "cli")]
 pub async fn neofetch() -> String {
     Neofetch::new().await.to_string()
@@ -49,7 +49,7 @@ pub async fn get_locale() -> crate::error::Result<String> {
 
         // Try /etc/default/locale (Ubuntu/Debian) or /etc/locale.conf (Arch)
         for path in ["/etc/default/locale", "/etc/locale.conf"] {
-            if let Ok(content) = tokio::fs::read_to_string(path).await {
+            if let Ok(content) = crate::utils::read_file_to_string(path).await {
                 for line in content.lines() {
                     if let Some(val) = line
                         .strip_prefix("LANG=")
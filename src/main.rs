@@ -1,5 +1,318 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use neofetch::cache::Cache;
+use neofetch::check::{self, Rules};
+use neofetch::config::{Config, parse_duration};
+use neofetch::explain::explain;
+use neofetch::history::{self, View};
+use neofetch::module::Module;
+use neofetch::output::{Format, load, render};
+use neofetch::redact::{self, Redact};
+use neofetch::report::{Column, Export, Filter, Report};
+use neofetch::theme::Mode;
+use neofetch::timings::Timings;
+use neofetch::watch::{progressive, watch};
+
+/// A fast, cross-platform system information tool
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Output format: text, json, yaml or toml
+    #[arg(short, long, global = true, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Configuration file (default: $XDG_CONFIG_HOME/neofetch/config.toml)
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Render a saved snapshot (json, yaml or toml) instead of the live system
+    #[arg(short, long, value_name = "FILE")]
+    load: Option<PathBuf>,
+
+    /// Redraw in place, refreshing uptime, load, memory, disks, temperatures,
+    /// battery and network every INTERVAL (e.g. 2, 1.5s, 500ms)
+    #[arg(short, long, value_name = "INTERVAL", value_parser = parse_duration, conflicts_with = "load")]
+    watch: Option<Duration>,
+
+    /// Report how long each collector and subprocess took, slowest first
+    #[arg(long, conflicts_with_all = ["load", "watch"])]
+    timings: bool,
+
+    /// List every module with its status, error, and the files and commands it used
+    #[arg(long, visible_alias = "debug", conflicts_with_all = ["load", "watch", "timings"])]
+    explain: bool,
+
+    /// Collect everything fresh, neither reading nor writing the cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Delete the cache before collecting
+    #[arg(long)]
+    clear_cache: bool,
+
+    /// Mask hostname, user name, IP and MAC addresses for sharing the output
+    #[arg(long, global = true)]
+    redact: bool,
+
+    /// Colors for a light or dark background: auto, light or dark
+    /// (default: the `theme` setting, else asking the terminal)
+    #[arg(long, value_name = "MODE")]
+    theme: Option<Mode>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compare two saved snapshots field by field
+    Diff {
+        /// Snapshot taken before the change
+        old: PathBuf,
+        /// Snapshot taken after the change
+        new: PathBuf,
+    },
+    /// Aggregate a directory of snapshots into a table with one row per host
+    Report {
+        /// Directory containing json, yaml or toml snapshots
+        dir: PathBuf,
+        /// Sort rows by column
+        #[arg(short, long)]
+        sort: Option<Column>,
+        /// Reverse the sort order
+        #[arg(short, long)]
+        reverse: bool,
+        /// Keep rows whose column contains a pattern, e.g. os=ubuntu (repeatable)
+        #[arg(long = "filter", value_name = "COLUMN=PATTERN")]
+        filters: Vec<Filter>,
+        /// Columns to show, comma separated (default: all)
+        #[arg(short, long, value_delimiter = ',')]
+        columns: Vec<Column>,
+        /// Table format: text, csv or markdown
+        #[arg(short, long, default_value = "text")]
+        export: Export,
+    },
+    /// Check the system against rules and exit with 1 when any fails
+    Check {
+        /// Smallest total memory, e.g. 16G (G = GB, GiB for binary)
+        #[arg(long, value_name = "SIZE")]
+        min_memory: Option<String>,
+        /// Kernel version with an optional operator (default >=), e.g. ">=6.1"
+        #[arg(long, value_name = "VERSION")]
+        kernel: Option<String>,
+        /// Distribution, e.g. ubuntu
+        #[arg(long)]
+        distro: Option<String>,
+        /// Highest usage of every disk, e.g. 80%
+        #[arg(long, value_name = "PERCENT")]
+        max_disk_usage: Option<String>,
+        /// Further rule such as "cpu.cores >= 8" (repeatable)
+        #[arg(long = "rule", value_name = "RULE")]
+        rules: Vec<String>,
+        /// TOML file with rules; command line rules are added to it
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+    },
+    /// Trends from the runs recorded in $XDG_DATA_HOME/neofetch/history.jsonl
+    History {
+        /// disks (growth per mount), packages, kernels or uptimes (longest first)
+        #[arg(default_value = "uptimes")]
+        view: View,
+    },
+    /// Browse every collected field: a module sidebar with full details,
+    /// search and export of the selected module
+    Explore,
+    /// Serve live system info over HTTP (/, /json, /metrics, /module/<name>)
+    Serve {
+        /// Address to listen on
+        #[arg(short, long, default_value = neofetch::serve::DEFAULT_BIND)]
+        bind: String,
+        /// Seconds to reuse collected results between requests
+        #[arg(long, value_name = "SECONDS", default_value_t = 30)]
+        cache: u64,
+    },
+}
+
+async fn run(args: Args) -> neofetch::Result<String> {
+    let mut config = Config::load(args.config.as_deref())?;
+    config.redact.enabled |= args.redact;
+    config.apply().await?;
+    match args.command {
+        Some(Command::Diff { old, new }) => {
+            let (old, new) = (load(old)?, load(new)?);
+            let entries = neofetch::diff::diff(&redact::apply(&old), &redact::apply(&new))?;
+            neofetch::diff::render(&entries, args.format)
+        }
+        Some(Command::Report {
+            dir,
+            sort,
+            reverse,
+            filters,
+            columns,
+            export,
+        }) => {
+            let mut report = Report::load_dir(dir)?;
+            for (path, e) in &report.skipped {
+                eprintln!("skipping {}: {e}", path.display());
+            }
+            report.filter(&filters);
+            if let Some(column) = sort {
+                report.sort_by(column, reverse);
+            }
+            report.redact(&neofetch::config::current().redact);
+            let columns = if columns.is_empty() {
+                Column::ALL.to_vec()
+            } else {
+                columns
+            };
+            Ok(report.render(&columns, export))
+        }
+        Some(Command::Check {
+            min_memory,
+            kernel,
+            distro,
+            max_disk_usage,
+            rules,
+            file,
+        }) => {
+            let mut all = match file {
+                Some(path) => Rules::load(path)?,
+                None => Rules::default(),
+            };
+            all.merge(Rules {
+                min_memory,
+                kernel,
+                distro,
+                max_disk_usage,
+                rules,
+            });
+            let rules = all.parse()?;
+            let neofetch = match &args.load {
+                Some(path) => load(path)?,
+                None => {
+                    let mut neofetch = neofetch::Neofetch::empty();
+                    neofetch.refresh(&check::modules(&rules)).await;
+                    neofetch
+                }
+            };
+            let mut outcomes = check::evaluate(&rules, &neofetch);
+            if let Some(scrubber) = redact::scrubber(&neofetch) {
+                check::scrub(&mut outcomes, &scrubber);
+            }
+            let output = check::render(&outcomes, args.format)?;
+            if outcomes.iter().all(|o| o.passed) {
+                return Ok(output);
+            }
+            println!("{output}");
+            std::process::exit(1);
+        }
+        Some(Command::History { view }) => {
+            let mut table = view.table(&history::load()?);
+            let redact = neofetch::config::current().redact;
+            if redact.enabled {
+                let mut neofetch = neofetch::Neofetch::empty();
+                neofetch.refresh(&Redact::MODULES).await;
+                table.scrub(&redact.scrubber(&neofetch));
+            }
+            table.render(args.format)
+        }
+        Some(Command::Explore) => {
+            let neofetch = match &args.load {
+                Some(path) => load(path)?,
+                None => collect(&args, false).await?,
+            };
+            neofetch::explore::explore(neofetch, args.format).await?;
+            Ok(String::new())
+        }
+        Some(Command::Serve { bind, cache }) => {
+            neofetch::serve::serve(&bind, Duration::from_secs(cache)).await?;
+            Ok(String::new())
+        }
+        None if args.explain => neofetch::explain::render(&explain().await, args.format),
+        None if args.timings => {
+            let (neofetch, timings) = Timings::collect().await;
+            timings.render(&neofetch, args.format)
+        }
+        None => {
+            let mode = args.theme.unwrap_or(neofetch::config::current().theme);
+            // Only a terminal can be asked for its background
+            if args.format == Format::Text
+                && (mode != Mode::Auto || std::io::stdout().is_terminal())
+            {
+                neofetch::theme::set(neofetch::theme::resolve(mode));
+            }
+            let neofetch = match &args.load {
+                Some(path) => load(path)?,
+                None => {
+                    let draw = args.watch.is_none()
+                        && args.format == Format::Text
+                        && std::io::stdout().is_terminal();
+                    let neofetch = collect(&args, draw).await?;
+                    record(&neofetch).await;
+                    if draw {
+                        return Ok(String::new());
+                    }
+                    neofetch
+                }
+            };
+            if let Some(interval) = args.watch {
+                watch(neofetch, interval, args.format).await?;
+                return Ok(String::new());
+            }
+            render(&neofetch, args.format)
+        }
+    }
+}
+
+/// Collect the live system, reusing cached modules unless disabled
+async fn collect(args: &Args, draw: bool) -> neofetch::Result<neofetch::Neofetch> {
+    if args.clear_cache {
+        Cache::clear()?;
+    }
+    let mut cache = match args.no_cache {
+        true => None,
+        false => Some(Cache::open().await),
+    };
+    let mut neofetch = neofetch::Neofetch::empty();
+    let pending = match &cache {
+        Some(cache) => cache.load_into(&mut neofetch, &Module::all()),
+        None => Module::all(),
+    };
+    if draw {
+        neofetch = progressive(neofetch, &pending).await?;
+    } else {
+        neofetch.refresh(&pending).await;
+    }
+    if let Some(cache) = &mut cache {
+        cache.store(&neofetch, &pending);
+        // The cache is only an optimization; a read-only home must not break the output
+        let _ = cache.save();
+    }
+    Ok(neofetch)
+}
+
+/// Append the run to the history, if enabled and the last record is old enough
+async fn record(neofetch: &neofetch::Neofetch) {
+    let history = neofetch::config::current().history;
+    if history.due() {
+        // Identifying data such as a user name in a mount point stays off the disk too
+        let record = history::Record::new(&redact::apply(neofetch)).await;
+        // Like the cache, a read-only home must not break the output
+        let _ = history::append(&record, history.max_records);
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    let neofetch = neofetch::Neofetch::new().await;
-    println!("{neofetch}")
+    match run(Args::parse()).await {
+        Ok(s) if s.is_empty() => {}
+        Ok(s) => println!("{s}"),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
 }
@@ -3,10 +3,41 @@
 //! Collects memory usage information including total, used, and available memory.
 
 use crate::error::{NeofetchError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Memory usage information
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Memory {
+    /// Total memory in bytes
+    #[serde(rename = "total_bytes")]
+    pub total: u64,
+    /// Used memory in bytes
+    #[serde(rename = "used_bytes")]
+    pub used: u64,
+}
+
+impl Display for Memory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = if self.total > 0 {
+            (self.used as f64 / self.total as f64 * 100.0) as u32
+        } else {
+            0
+        };
+        let units = crate::units::current();
+        write!(
+            f,
+            "{} / {} ({}%)",
+            units.bytes(self.used as f64),
+            units.bytes(self.total as f64),
+            percent,
+        )
+    }
+}
 
 /// Get memory information on Linux / Android
 #[cfg(any(target_os = "linux", target_os = "android"))]
-pub async fn get_memory() -> Result<String> {
+pub async fn get_memory() -> Result<Memory> {
     use crate::utils::{parse_proc_file, read_file_to_string};
 
     let content = read_file_to_string("/proc/meminfo").await?;
@@ -36,20 +67,16 @@ pub async fn get_memory() -> Result<String> {
 
     // Calculate used memory
     let used_kb = total_kb - free_kb;
-    let usage_percent = (used_kb / total_kb * 100.0) as u32;
-
-    use human_bytes::human_bytes;
-    Ok(format!(
-        "{} / {} ({}%)",
-        human_bytes(used_kb * 1024.0),
-        human_bytes(total_kb * 1024.0),
-        usage_percent,
-    ))
+
+    Ok(Memory {
+        total: (total_kb * 1024.0) as u64,
+        used: (used_kb * 1024.0) as u64,
+    })
 }
 
 /// Get memory information on macOS
 #[cfg(target_os = "macos")]
-pub async fn get_memory() -> Result<String> {
+pub async fn get_memory() -> Result<Memory> {
     use crate::platform::macos;
     use crate::utils::execute_command;
 
@@ -77,15 +104,10 @@ pub async fn get_memory() -> Result<String> {
     let free_total_pages = free_pages + inactive_pages;
     let used_bytes = used_pages * page_size_bytes;
 
-    let usage_percent = (used_bytes as f64 / total_bytes as f64 * 100.0) as u32;
-
-    use human_bytes::human_bytes;
-    Ok(format!(
-        "{} / {} ({}%)",
-        human_bytes(used_bytes as f64),
-        human_bytes(total_bytes as f64),
-        usage_percent,
-    ))
+    Ok(Memory {
+        total: total_bytes,
+        used: used_bytes,
+    })
 }
 
 /// Get memory information on other Unix systems
@@ -93,13 +115,13 @@ pub async fn get_memory() -> Result<String> {
     unix,
     not(any(target_os = "linux", target_os = "android", target_os = "macos"))
 ))]
-pub async fn get_memory() -> Result<String> {
+pub async fn get_memory() -> Result<Memory> {
     Err(NeofetchError::UnsupportedPlatform)
 }
 
 /// Get memory information on Windows
 #[cfg(windows)]
-pub async fn get_memory() -> Result<String> {
+pub async fn get_memory() -> Result<Memory> {
     use serde::Deserialize;
 
     #[derive(Deserialize, Debug, Clone)]
@@ -120,17 +142,12 @@ pub async fn get_memory() -> Result<String> {
         .first()
         .ok_or_else(|| NeofetchError::data_unavailable("No memory information found"))?;
 
-    let used_kb = (info.total_visible_memory_size - info.free_physical_memory) as f64;
-    let total_kb = info.total_visible_memory_size as f64;
-    let usage_percent = (used_kb / total_kb * 100.0) as u32;
-
-    use human_bytes::human_bytes;
+    let used_kb = info.total_visible_memory_size - info.free_physical_memory;
+    let total_kb = info.total_visible_memory_size;
 
     use crate::platform::wmi_query;
-    Ok(format!(
-        "{} / {} ({}%)",
-        human_bytes(used_kb * 1024.0),
-        human_bytes(total_kb * 1024.0),
-        usage_percent
-    ))
+    Ok(Memory {
+        total: total_kb * 1024,
+        used: used_kb * 1024,
+    })
 }
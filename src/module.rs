@@ -0,0 +1,289 @@
+//! Information modules
+//!
+//! Names every collected field of [`Neofetch`] so a single result can be
+//! addressed or re-collected on its own, e.g. by the HTTP server's
+//! `/module/<name>` endpoint or [`Neofetch::refresh`]. Lines added by a
+//! registered [`Collector`](crate::collector::Collector) are modules too.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::Neofetch;
+use crate::collector;
+use crate::error::{NeofetchError, Result};
+
+/// One collected field of [`Neofetch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Module {
+    Os,
+    User,
+    Host,
+    Hostname,
+    Rom,
+    Baseband,
+    Kernel,
+    Uptime,
+    LoadAverage,
+    Packages,
+    Shell,
+    Display,
+    De,
+    Wm,
+    WmTheme,
+    Terminal,
+    Disk,
+    Cpu,
+    Gpu,
+    Memory,
+    Battery,
+    Locale,
+    Ip,
+    LocalIp,
+    Temperature,
+    Network,
+    /// Line added by a registered [`Collector`](crate::collector::Collector)
+    Extra(&'static str),
+}
+
+impl Module {
+    /// All built-in modules in field order
+    pub const ALL: [Module; 26] = [
+        Module::Os,
+        Module::User,
+        Module::Host,
+        Module::Hostname,
+        Module::Rom,
+        Module::Baseband,
+        Module::Kernel,
+        Module::Uptime,
+        Module::LoadAverage,
+        Module::Packages,
+        Module::Shell,
+        Module::Display,
+        Module::De,
+        Module::Wm,
+        Module::WmTheme,
+        Module::Terminal,
+        Module::Disk,
+        Module::Cpu,
+        Module::Gpu,
+        Module::Memory,
+        Module::Battery,
+        Module::Locale,
+        Module::Ip,
+        Module::LocalIp,
+        Module::Temperature,
+        Module::Network,
+    ];
+
+    /// Built-in modules followed by the registered collectors
+    pub fn all() -> Vec<Module> {
+        let extra = collector::registered();
+        Module::ALL
+            .into_iter()
+            .chain(extra.iter().map(|c| Module::Extra(c.name())))
+            .collect()
+    }
+
+    /// Modules whose values change while the system runs
+    pub fn volatile() -> Vec<Module> {
+        let extra = collector::registered();
+        [
+            Module::Uptime,
+            Module::LoadAverage,
+            Module::Disk,
+            Module::Memory,
+            Module::Battery,
+            Module::LocalIp,
+            Module::Temperature,
+            Module::Network,
+        ]
+        .into_iter()
+        .chain(
+            extra
+                .iter()
+                .filter(|c| c.volatile())
+                .map(|c| Module::Extra(c.name())),
+        )
+        .collect()
+    }
+
+    /// Field name, e.g. `wm_theme`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Module::Os => "os",
+            Module::User => "user",
+            Module::Host => "host",
+            Module::Hostname => "hostname",
+            Module::Rom => "rom",
+            Module::Baseband => "baseband",
+            Module::Kernel => "kernel",
+            Module::Uptime => "uptime",
+            Module::LoadAverage => "load_average",
+            Module::Packages => "packages",
+            Module::Shell => "shell",
+            Module::Display => "display",
+            Module::De => "de",
+            Module::Wm => "wm",
+            Module::WmTheme => "wm_theme",
+            Module::Terminal => "terminal",
+            Module::Disk => "disk",
+            Module::Cpu => "cpu",
+            Module::Gpu => "gpu",
+            Module::Memory => "memory",
+            Module::Battery => "battery",
+            Module::Locale => "locale",
+            Module::Ip => "ip",
+            Module::LocalIp => "local_ip",
+            Module::Temperature => "temperature",
+            Module::Network => "network",
+            Module::Extra(name) => name,
+        }
+    }
+}
+
+impl FromStr for Module {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.to_lowercase().replace('-', "_");
+        Module::all()
+            .into_iter()
+            .find(|m| m.name() == name)
+            .ok_or_else(|| format!("unknown module '{s}'"))
+    }
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn to_value<T: Serialize>(result: &Result<T>) -> Result<Value> {
+    match result {
+        Ok(v) => serde_json::to_value(v).map_err(|e| NeofetchError::serialization(e.to_string())),
+        Err(e) => Err(e.clone()),
+    }
+}
+
+fn from_value<T: DeserializeOwned>(value: Result<Value>) -> Result<T> {
+    value.and_then(|v| {
+        serde_json::from_value(v).map_err(|e| NeofetchError::parse_error("value", e.to_string()))
+    })
+}
+
+impl Neofetch {
+    /// Structured value of a single module, as it appears in the JSON snapshot
+    pub fn value(&self, module: Module) -> Result<Value> {
+        match module {
+            Module::Os => to_value(&self.os),
+            Module::User => to_value(&self.user),
+            Module::Host => to_value(&self.host),
+            Module::Hostname => to_value(&self.hostname),
+            Module::Rom => to_value(&self.rom),
+            Module::Baseband => to_value(&self.baseband),
+            Module::Kernel => to_value(&self.kernel),
+            Module::Uptime => to_value(&self.uptime),
+            Module::LoadAverage => to_value(&self.load_average),
+            Module::Packages => to_value(&self.packages),
+            Module::Shell => to_value(&self.shell),
+            Module::Display => to_value(&self.display),
+            Module::De => to_value(&self.de),
+            Module::Wm => to_value(&self.wm),
+            Module::WmTheme => to_value(&self.wm_theme),
+            Module::Terminal => to_value(&self.terminal),
+            Module::Disk => to_value(&self.disk),
+            Module::Cpu => to_value(&self.cpu),
+            Module::Gpu => to_value(&self.gpu),
+            Module::Memory => to_value(&self.memory),
+            Module::Battery => to_value(&self.battery),
+            Module::Locale => to_value(&self.locale),
+            Module::Ip => to_value(&self.ip),
+            Module::LocalIp => to_value(&self.local_ip),
+            Module::Temperature => to_value(&self.temperature),
+            Module::Network => to_value(&self.network),
+            Module::Extra(name) => self
+                .extra
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| Err(NeofetchError::data_unavailable("not collected"))),
+        }
+    }
+
+    /// Store a structured value (e.g. from [`Neofetch::stream`]) into its field
+    pub fn set_value(&mut self, module: Module, value: Result<Value>) {
+        match module {
+            Module::Os => self.os = from_value(value),
+            Module::User => self.user = from_value(value),
+            Module::Host => self.host = from_value(value),
+            Module::Hostname => self.hostname = from_value(value),
+            Module::Rom => self.rom = from_value(value),
+            Module::Baseband => self.baseband = from_value(value),
+            Module::Kernel => self.kernel = from_value(value),
+            Module::Uptime => self.uptime = from_value(value),
+            Module::LoadAverage => self.load_average = from_value(value),
+            Module::Packages => self.packages = from_value(value),
+            Module::Shell => self.shell = from_value(value),
+            Module::Display => self.display = from_value(value),
+            Module::De => self.de = from_value(value),
+            Module::Wm => self.wm = from_value(value),
+            Module::WmTheme => self.wm_theme = from_value(value),
+            Module::Terminal => self.terminal = from_value(value),
+            Module::Disk => self.disk = from_value(value),
+            Module::Cpu => self.cpu = from_value(value),
+            Module::Gpu => self.gpu = from_value(value),
+            Module::Memory => self.memory = from_value(value),
+            Module::Battery => self.battery = from_value(value),
+            Module::Locale => self.locale = from_value(value),
+            Module::Ip => self.ip = from_value(value),
+            Module::LocalIp => self.local_ip = from_value(value),
+            Module::Temperature => self.temperature = from_value(value),
+            Module::Network => self.network = from_value(value),
+            Module::Extra(name) => self.extra.set(name, value),
+        }
+    }
+
+    /// Like [`Neofetch::value`], with errors as `{ error = { kind, detail } }` entries
+    pub fn entry(&self, module: Module) -> Value {
+        self.value(module).unwrap_or_else(|e| json!({ "error": e }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_from_str() {
+        assert_eq!("wm-theme".parse::<Module>(), Ok(Module::WmTheme));
+        assert_eq!("CPU".parse::<Module>(), Ok(Module::Cpu));
+        assert!("bogus".parse::<Module>().is_err());
+        for module in Module::ALL {
+            assert_eq!(module.to_string().parse::<Module>(), Ok(module));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh() {
+        let mut neofetch = Neofetch::empty();
+        neofetch.refresh(&[Module::Kernel]).await;
+        assert!(neofetch.kernel.is_ok());
+        assert!(neofetch.user.is_err());
+        assert!(!Module::volatile().contains(&Module::Cpu));
+    }
+
+    #[test]
+    fn test_set_value() {
+        let mut neofetch = Neofetch::empty();
+        neofetch.set_value(Module::Kernel, Ok(json!("6.8.0")));
+        assert_eq!(neofetch.kernel.as_deref().ok(), Some("6.8.0"));
+        neofetch.set_value(Module::Cpu, Ok(json!("not a cpu")));
+        assert!(neofetch.cpu.is_err());
+        assert_eq!(neofetch.value(Module::Kernel).ok(), Some(json!("6.8.0")));
+    }
+}
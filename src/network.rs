@@ -3,10 +3,11 @@
 //! Collects network interface information including IP addresses and status.
 
 use crate::error::{NeofetchError, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// Network interface information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInfo {
     /// Interface name (e.g., "eth0", "wlan0")
     pub interface_name: String,
@@ -3,9 +3,10 @@
 //! Collects OS name, version, and distribution information across platforms.
 
 use crate::error::{NeofetchError, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Distro {
     Aix,
     AlmaLinux,
@@ -503,7 +504,7 @@ impl Display for Distro {
         f.write_str(s)
     }
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OS {
     pub distro: Distro,
     pub name: String,
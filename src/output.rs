@@ -0,0 +1,235 @@
+//! Structured output formats and snapshots
+//!
+//! Serializes a collected [`Neofetch`] into machine-readable documents.
+//! Byte and temperature values are emitted as raw numbers with the unit in the
+//! key name (`total_bytes`, `temperature_celsius`), and fields that failed to
+//! collect appear as `{ error = { kind, detail } }` entries instead of being dropped.
+//!
+//! Every structured format round-trips, so a saved snapshot can be loaded with
+//! [`load`] and rendered exactly as if it had been collected locally.
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::Neofetch;
+use crate::error::{NeofetchError, Result};
+
+/// Output format for the collected information
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Logo with colored info lines
+    #[default]
+    Text,
+    /// JSON document
+    Json,
+    /// YAML document
+    Yaml,
+    /// TOML document
+    Toml,
+}
+
+impl Format {
+    /// Guess the snapshot format from a file extension, defaulting to JSON
+    pub fn from_path(path: impl AsRef<Path>) -> Format {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some("yaml" | "yml") => Format::Yaml,
+            Some("toml") => Format::Toml,
+            _ => Format::Json,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "toml" => Ok(Format::Toml),
+            _ => Err(format!(
+                "unknown format '{s}' (expected text, json, yaml or toml)"
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Yaml => "yaml",
+            Format::Toml => "toml",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Serialize to a JSON document
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    serde_json::to_string_pretty(value).map_err(|e| NeofetchError::serialization(e.to_string()))
+}
+
+/// Serialize to a YAML document
+pub fn to_yaml<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    serde_yaml_ng::to_string(value).map_err(|e| NeofetchError::serialization(e.to_string()))
+}
+
+/// Serialize to a TOML document
+pub fn to_toml<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    toml::to_string_pretty(value).map_err(|e| NeofetchError::serialization(e.to_string()))
+}
+
+/// Serialize to a structured format; text has no structured form
+pub fn serialize<T: Serialize + ?Sized>(value: &T, format: Format) -> Result<String> {
+    match format {
+        Format::Text => Err(NeofetchError::serialization(
+            "text is not a structured format",
+        )),
+        Format::Json => to_json(value),
+        Format::Yaml => to_yaml(value),
+        Format::Toml => to_toml(value),
+    }
+}
+
+/// Serialize a list to a structured format
+///
+/// TOML documents cannot be arrays, so there the items become a `[[key]]` array of tables.
+pub fn serialize_list<T: Serialize>(key: &str, items: &[T], format: Format) -> Result<String> {
+    match format {
+        Format::Toml => to_toml(&std::collections::BTreeMap::from([(key, items)])),
+        _ => serialize(items, format),
+    }
+}
+
+/// `neofetch` as it is shown: with the scripts applied, then redacted
+pub fn prepare(neofetch: &Neofetch) -> Cow<'_, Neofetch> {
+    match crate::script::apply(neofetch) {
+        Cow::Borrowed(neofetch) => crate::redact::apply(neofetch),
+        Cow::Owned(scripted) => Cow::Owned(crate::redact::apply(&scripted).into_owned()),
+    }
+}
+
+/// Render in the requested format
+pub fn render(neofetch: &Neofetch, format: Format) -> Result<String> {
+    let neofetch = &*prepare(neofetch);
+    match format {
+        Format::Text => Ok(neofetch.to_string()),
+        _ => serialize(neofetch, format),
+    }
+}
+
+/// Parse a snapshot previously written with [`render`]
+pub fn parse(content: &str, format: Format) -> Result<Neofetch> {
+    let parsed = match format {
+        Format::Text => Err("text output cannot be loaded".to_string()),
+        Format::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        Format::Yaml => serde_yaml_ng::from_str(content).map_err(|e| e.to_string()),
+        Format::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+    };
+    parsed.map_err(|e| NeofetchError::parse_error("snapshot", e))
+}
+
+/// Load a snapshot file, picking the format from its extension
+pub fn load(path: impl AsRef<Path>) -> Result<Neofetch> {
+    let path = path.as_ref();
+    let content = crate::utils::read_file_to_string_sync(path)?;
+    parse(&content, Format::from_path(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disk::Disk;
+    use crate::memory::Memory;
+
+    fn unsupported<T>() -> Result<T> {
+        Err(NeofetchError::UnsupportedPlatform)
+    }
+
+    fn sample() -> Neofetch {
+        Neofetch {
+            os: unsupported(),
+            user: Ok("alice".into()),
+            host: unsupported(),
+            hostname: Ok("box".into()),
+            rom: unsupported(),
+            baseband: unsupported(),
+            kernel: Ok("6.8.0".into()),
+            uptime: unsupported(),
+            load_average: unsupported(),
+            packages: unsupported(),
+            shell: unsupported(),
+            display: unsupported(),
+            de: unsupported(),
+            wm: unsupported(),
+            wm_theme: unsupported(),
+            terminal: unsupported(),
+            disk: Ok(vec![Disk {
+                name: "/".into(),
+                total: 1024,
+                used: 512,
+            }]),
+            cpu: unsupported(),
+            gpu: unsupported(),
+            memory: Ok(Memory {
+                total: 2048,
+                used: 1024,
+            }),
+            battery: Err(NeofetchError::data_unavailable("no battery")),
+            locale: unsupported(),
+            ip: unsupported(),
+            local_ip: unsupported(),
+            temperature: unsupported(),
+            network: unsupported(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("YAML".parse::<Format>(), Ok(Format::Yaml));
+        assert_eq!("toml".parse::<Format>(), Ok(Format::Toml));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path("host.yml"), Format::Yaml);
+        assert_eq!(Format::from_path("host.toml"), Format::Toml);
+        assert_eq!(Format::from_path("host.json"), Format::Json);
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let original = sample();
+        for format in [Format::Json, Format::Yaml, Format::Toml] {
+            let content = render(&original, format).unwrap();
+            let loaded = parse(&content, format).unwrap();
+            assert_eq!(loaded.to_string(), original.to_string());
+            assert_eq!(render(&loaded, format).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_to_yaml() {
+        let yaml = to_yaml(&sample()).unwrap();
+        assert!(yaml.contains("kernel: 6.8.0"));
+        assert!(yaml.contains("total_bytes: 1024"));
+        assert!(yaml.contains("kind: UnsupportedPlatform"));
+    }
+
+    #[test]
+    fn test_to_toml() {
+        let toml = to_toml(&sample()).unwrap();
+        assert!(toml.contains("kernel = \"6.8.0\""));
+        assert!(toml.contains("used_bytes = 1024"));
+        assert!(toml.contains("detail = \"no battery\""));
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Packages {
     snap: usize,
     dpkg: usize,
@@ -7,32 +7,42 @@ pub struct Packages {
     opkg: usize,
 }
 
+impl Packages {
+    /// Total number of packages across all package managers
+    pub fn total(&self) -> usize {
+        self.snap + self.dpkg + self.pacman + self.scoop + self.opkg
+    }
+
+    /// Package count per package manager
+    pub fn counts(&self) -> [(&'static str, usize); 5] {
+        [
+            ("dpkg", self.dpkg),
+            ("snap", self.snap),
+            ("pacman", self.pacman),
+            ("scoop", self.scoop),
+            ("opkg", self.opkg),
+        ]
+    }
+}
+
 impl Display for Packages {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut v = Vec::new();
-        if self.dpkg > 0 {
-            v.push(format!("{} (dpkg)", self.dpkg))
-        }
-        if self.snap > 0 {
-            v.push(format!("{} (snap)", self.snap))
-        }
-        if self.pacman > 0 {
-            v.push(format!("{} (pacman)", self.pacman))
-        }
-        if self.scoop > 0 {
-            v.push(format!("{} (scoop)", self.scoop))
-        }
-        if self.opkg > 0 {
-            v.push(format!("{} (opkg)", self.opkg))
-        }
+        let v: Vec<_> = self
+            .counts()
+            .iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(name, n)| format!("{n} ({name})"))
+            .collect();
 
         f.write_str(&v.join(", "))
     }
 }
 
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use crate::error::{NeofetchError, Result};
+use crate::utils::file::{count_dir_entries, read_file_to_string_sync};
 use crate::utils::process::execute_command_sync;
 
 /// Build a platform-aware Unix path (handles MSYS2 on Windows)
@@ -53,36 +63,48 @@ fn unix_path(unix: &str, windows_msys: &str) -> std::path::PathBuf {
     }
 }
 
+/// Latest modification time of the package databases, in seconds since the epoch
+///
+/// Changes whenever a package manager installs or removes something, which
+/// makes it a cheap invalidation key for cached package counts.
+pub fn database_mtime() -> Option<u64> {
+    let mut paths = vec![
+        unix_path("/var/lib/dpkg/status", "var/lib/dpkg/status"),
+        unix_path("/var/lib/pacman/local", "var/lib/pacman/local"),
+        unix_path("/var/lib/snapd/snaps", "var/lib/snapd/snaps"),
+        unix_path("/usr/lib/opkg/status", "usr/lib/opkg/status"),
+    ];
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.join("scoop").join("apps"));
+    }
+    paths
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok()?.modified().ok())
+        .filter_map(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .max()
+}
+
 fn pacman() -> Result<usize> {
     let dir = unix_path("/var/lib/pacman/local", "var/lib/pacman/local");
-    let count = std::fs::read_dir(&dir)
-        .map_err(|e| NeofetchError::file_read(dir.display().to_string(), e))?
-        .count();
-    Ok(count.saturating_sub(1))
+    Ok(count_dir_entries(dir)?.saturating_sub(1))
 }
 
 fn snap() -> Result<usize> {
     let dir = unix_path("/var/lib/snapd/snaps", "var/lib/snapd/snaps");
-    let count = std::fs::read_dir(&dir)
-        .map_err(|e| NeofetchError::file_read(dir.display().to_string(), e))?
-        .count();
-    Ok(count.saturating_sub(1))
+    Ok(count_dir_entries(dir)?.saturating_sub(1))
 }
 
 fn scoop() -> Result<usize> {
     let home_dir = dirs::home_dir()
         .ok_or_else(|| NeofetchError::data_unavailable("Home directory not found"))?;
     let dir = home_dir.join("scoop").join("apps");
-    let count = std::fs::read_dir(&dir)
-        .map_err(|e| NeofetchError::file_read(dir.display().to_string(), e))?
-        .count();
-    Ok(count.saturating_sub(1))
+    Ok(count_dir_entries(dir)?.saturating_sub(1))
 }
 
 fn dpkg() -> Result<usize> {
     let dir = unix_path("/var/lib/dpkg/status", "var/lib/dpkg/status");
-    let file = std::fs::read_to_string(&dir)
-        .map_err(|e| NeofetchError::file_read(dir.display().to_string(), e))?;
+    let file = read_file_to_string_sync(dir)?;
     let package_count = file
         .lines()
         .filter(|line| line.starts_with("Package:"))
@@ -0,0 +1,193 @@
+//! Color blocks below the info lines
+//!
+//! Blocks made of spaces are painted with the background color; any other
+//! block (e.g. `█` or `● `) is drawn in the foreground color.
+
+use serde::Deserialize;
+
+use crate::color::RESET;
+use crate::error::{NeofetchError, Result};
+
+/// Steps of the truecolor gradient rows
+const GRADIENT_STEPS: usize = 36;
+
+/// Colors shown by the palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteStyle {
+    /// The 8 normal and 8 bright colors of the terminal theme
+    #[default]
+    Ansi,
+    /// The 16 theme colors, the 6×6×6 color cube and the grayscale ramp
+    #[serde(rename = "256")]
+    Cube,
+    /// A 24-bit hue and grayscale gradient
+    Truecolor,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Color {
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn escape(self, background: bool) -> String {
+        match self {
+            // Theme colors keep their short codes (`ESC[41m`, `ESC[101m`, ...)
+            Color::Indexed(n @ 0..=7) => format!("\x1b[{}m", n + if background { 40 } else { 30 }),
+            Color::Indexed(n @ 8..=15) => {
+                format!("\x1b[{}m", n - 8 + if background { 100 } else { 90 })
+            }
+            Color::Indexed(n) => format!("\x1b[{};5;{n}m", if background { 48 } else { 38 }),
+            Color::Rgb(r, g, b) => {
+                format!("\x1b[{};2;{r};{g};{b}m", if background { 48 } else { 38 })
+            }
+        }
+    }
+}
+
+/// Palette settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Palette {
+    pub style: PaletteStyle,
+    /// Times the block text is repeated per color
+    pub width: usize,
+    /// Lines per row of colors
+    pub height: usize,
+    /// Text of one block
+    pub block: String,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            style: PaletteStyle::Ansi,
+            width: 3,
+            height: 1,
+            block: " ".into(),
+        }
+    }
+}
+
+/// Fully saturated color of a hue in degrees
+fn hue(degrees: f64) -> Color {
+    let x = 1.0 - ((degrees / 60.0) % 2.0 - 1.0).abs();
+    let (r, g, b) = match degrees as u32 / 60 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let byte = |v: f64| (v * 255.0).round() as u8;
+    Color::Rgb(byte(r), byte(g), byte(b))
+}
+
+impl Palette {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.width == 0 || self.height == 0 || self.block.is_empty() {
+            return Err(NeofetchError::config(
+                "palette: width and height must be at least 1 and block must not be empty",
+            ));
+        }
+        Ok(())
+    }
+
+    fn rows(&self) -> Vec<Vec<Color>> {
+        fn indexed(range: impl Iterator<Item = u8>) -> Vec<Color> {
+            range.map(Color::Indexed).collect()
+        }
+        match self.style {
+            PaletteStyle::Off => Vec::new(),
+            PaletteStyle::Ansi => vec![indexed(0..8), indexed(8..16)],
+            PaletteStyle::Cube => {
+                let mut rows = vec![indexed(0..8), indexed(8..16)];
+                // Two 3×6 slabs of the cube side by side, one green level per row
+                for reds in [0..3, 3..6] {
+                    for g in 0..6 {
+                        let row = reds
+                            .clone()
+                            .flat_map(|r| {
+                                (0..6).map(move |b| Color::Indexed(16 + 36 * r + 6 * g + b))
+                            })
+                            .collect();
+                        rows.push(row);
+                    }
+                }
+                rows.push(indexed(232..244));
+                rows.push(indexed(244..=255));
+                rows
+            }
+            PaletteStyle::Truecolor => {
+                let steps = 0..GRADIENT_STEPS;
+                let hues = steps
+                    .clone()
+                    .map(|i| hue(i as f64 * 360.0 / GRADIENT_STEPS as f64));
+                let grays = steps.map(|i| {
+                    let v = (i * 255 / (GRADIENT_STEPS - 1)) as u8;
+                    Color::Rgb(v, v, v)
+                });
+                vec![hues.collect(), grays.collect()]
+            }
+        }
+    }
+
+    /// The palette lines, empty when turned off
+    pub fn render(&self) -> String {
+        let background = self.block.trim().is_empty();
+        let block = self.block.repeat(self.width);
+        let mut s = String::new();
+        for row in self.rows() {
+            let line: String = row
+                .iter()
+                .map(|c| format!("{}{block}", c.escape(background)))
+                .collect();
+            for _ in 0..self.height {
+                s.push_str(&line);
+                s.push_str(RESET);
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{BLACK_BG, BRIGHT_WHITE_BG};
+    use crate::config::Config;
+
+    #[test]
+    fn test_palette() {
+        let ansi = Palette::default().render();
+        assert_eq!(ansi.lines().count(), 2);
+        assert!(ansi.starts_with(&format!("{BLACK_BG}   ")));
+        assert!(ansi.contains(&format!("{BRIGHT_WHITE_BG}   {RESET}\n")));
+
+        let cube = Palette {
+            style: PaletteStyle::Cube,
+            width: 1,
+            height: 2,
+            block: "█".into(),
+        };
+        let cube = cube.render();
+        assert_eq!(cube.lines().count(), 2 * 16);
+        assert!(cube.contains("\x1b[38;5;231m█"));
+
+        let off = Palette {
+            style: PaletteStyle::Off,
+            ..Default::default()
+        };
+        assert_eq!(off.render(), "");
+    }
+
+    #[test]
+    fn test_palette_config() {
+        assert!(Config::parse("palette = { style = '256', width = 0 }").is_err());
+    }
+}
@@ -116,8 +116,7 @@ pub fn get_thermal_zones() -> Result<Vec<String>> {
         return Ok(Vec::new());
     }
 
-    let entries = std::fs::read_dir(thermal_path)
-        .map_err(|e| NeofetchError::file_read("/sys/class/thermal".to_string(), e))?;
+    let entries = crate::utils::file::read_dir(thermal_path)?;
 
     let mut zones = Vec::new();
     for entry in entries.flatten() {
@@ -51,6 +51,26 @@ pub async fn get_cpu_frequency() -> Result<u64> {
     Ok(hz / 1_000_000) // Convert to MHz
 }
 
+/// Get the number of logical CPUs (hardware threads)
+pub async fn get_cpu_thread_count() -> Result<u32> {
+    let output = sysctl("hw.logicalcpu").await?;
+    output.trim().parse().map_err(|e: std::num::ParseIntError| {
+        crate::error::NeofetchError::parse_error("cpu_thread_count", e.to_string())
+    })
+}
+
+/// Get maximum CPU frequency in MHz
+pub async fn get_cpu_max_frequency() -> Result<u64> {
+    let output = sysctl("hw.cpufrequency_max").await?;
+    let hz: u64 = output
+        .trim()
+        .parse()
+        .map_err(|e: std::num::ParseIntError| {
+            crate::error::NeofetchError::parse_error("cpu_max_frequency", e.to_string())
+        })?;
+    Ok(hz / 1_000_000) // Convert to MHz
+}
+
 /// Get total physical memory in bytes
 pub async fn get_memory_total() -> Result<u64> {
     let output = sysctl("hw.memsize").await?;
@@ -0,0 +1,367 @@
+//! Redaction of identifying data for sharing output
+//!
+//! Hostname, user name, public and local IP addresses and MAC addresses are
+//! masked (`x.x.x.x`) or hashed (`host-1f3a9c2e`) in their own fields, and every
+//! other occurrence of the same value, such as the hostname in the title or a
+//! user name in a path, is replaced the same way. Redaction happens before
+//! rendering, so every output format shows the same replacements.
+//!
+//! The public IP, which includes the city, is hidden by default and then not
+//! looked up at all.
+
+use std::borrow::Cow;
+use std::net::IpAddr;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::Neofetch;
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+
+/// What happens to a redacted field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Policy {
+    /// Shown unchanged
+    Keep,
+    /// Replaced by a placeholder such as `hostname` or `x.x.x.x`
+    Mask,
+    /// Replaced by a short hash, so equal values stay recognizable
+    Hash,
+    /// Left out, like a module that failed
+    Hide,
+}
+
+/// Redaction settings
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Redact {
+    /// Redact the output (also enabled with `--redact`)
+    pub enabled: bool,
+    pub hostname: Policy,
+    pub user: Policy,
+    /// Public IP address and location
+    pub ip: Policy,
+    /// Local IP and the addresses of the network interfaces
+    pub local_ip: Policy,
+    pub mac: Policy,
+}
+
+impl Default for Redact {
+    fn default() -> Self {
+        Redact {
+            enabled: false,
+            hostname: Policy::Mask,
+            user: Policy::Mask,
+            ip: Policy::Hide,
+            local_ip: Policy::Mask,
+            mac: Policy::Mask,
+        }
+    }
+}
+
+/// Kind of a redacted value, deciding its placeholder and hash prefix
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Hostname,
+    User,
+    Ip,
+    Mac,
+}
+
+/// 64-bit FNV-1a, stable across runs and platforms
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl Kind {
+    /// Replacement of `value`; hidden values are masked where they occur in other fields
+    fn replace(self, policy: Policy, value: &str) -> Option<String> {
+        match policy {
+            Policy::Keep => None,
+            Policy::Hash => {
+                let prefix = match self {
+                    Kind::Hostname => "host",
+                    Kind::User => "user",
+                    Kind::Ip => "ip",
+                    Kind::Mac => "mac",
+                };
+                Some(format!("{prefix}-{:08x}", fnv1a(value) as u32))
+            }
+            Policy::Mask | Policy::Hide => Some(
+                match self {
+                    Kind::Hostname => "hostname",
+                    Kind::User => "user",
+                    Kind::Ip if value.contains(':') => "x:x:x:x",
+                    Kind::Ip => "x.x.x.x",
+                    Kind::Mac => "xx:xx:xx:xx:xx:xx",
+                }
+                .to_string(),
+            ),
+        }
+    }
+}
+
+impl Redact {
+    /// Modules holding identifying data, to collect when redacting output that has no snapshot
+    pub const MODULES: [Module; 4] = [
+        Module::Hostname,
+        Module::User,
+        Module::LocalIp,
+        Module::Network,
+    ];
+
+    /// Whether `module` is not collected because it would be hidden entirely
+    pub fn skips(&self, module: Module) -> bool {
+        self.enabled && module == Module::Ip && self.ip == Policy::Hide
+    }
+
+    /// `neofetch` with the identifying data redacted
+    pub fn redact(&self, neofetch: &Neofetch) -> Neofetch {
+        let mut neofetch = neofetch.clone();
+        let scrubber = self.redact_fields(&mut neofetch);
+        let Ok(mut value) = serde_json::to_value(&neofetch) else {
+            return neofetch;
+        };
+        scrubber.value(&mut value);
+        serde_json::from_value(value).unwrap_or(neofetch)
+    }
+
+    /// Replacer of the identifying data of `neofetch` in other text, such as
+    /// error messages, paths or rendered tables
+    pub fn scrubber(&self, neofetch: &Neofetch) -> Scrubber {
+        self.redact_fields(&mut neofetch.clone())
+    }
+
+    /// Redact the identifying fields of `neofetch`, returning the replacer for
+    /// their values elsewhere
+    fn redact_fields(&self, neofetch: &mut Neofetch) -> Scrubber {
+        // (original, replacement) pairs to replace in every other field
+        let mut secrets = Vec::new();
+        let mut field = |value: &mut Result<String>, kind: Kind, policy: Policy| {
+            let Ok(original) = value.as_ref() else {
+                return;
+            };
+            // The public IP is followed by its location, e.g. `192.0.2.1 (DE-Berlin)`
+            let address = original.split(' ').next().unwrap_or_default().to_string();
+            let Some(replacement) = kind.replace(policy, &address) else {
+                return;
+            };
+            secrets.push((original.clone(), replacement.clone()));
+            secrets.push((address, replacement.clone()));
+            *value = match policy {
+                Policy::Hide => Err(NeofetchError::data_unavailable("redacted")),
+                _ => Ok(replacement),
+            };
+        };
+        field(&mut neofetch.hostname, Kind::Hostname, self.hostname);
+        field(&mut neofetch.user, Kind::User, self.user);
+        field(&mut neofetch.ip, Kind::Ip, self.ip);
+        field(&mut neofetch.local_ip, Kind::Ip, self.local_ip);
+
+        if let Ok(interfaces) = &mut neofetch.network {
+            for interface in interfaces {
+                let addresses = [&mut interface.ipv4_address, &mut interface.ipv6_address];
+                for address in addresses {
+                    // Loopback addresses are the same everywhere
+                    let loopback = address
+                        .as_deref()
+                        .and_then(|a| a.parse::<IpAddr>().ok())
+                        .is_some_and(|ip| ip.is_loopback());
+                    if !loopback {
+                        redact_option(address, Kind::Ip, self.local_ip, &mut secrets);
+                    }
+                }
+                redact_option(
+                    &mut interface.mac_address,
+                    Kind::Mac,
+                    self.mac,
+                    &mut secrets,
+                );
+            }
+        }
+
+        // Longest first, so a full value is replaced before a part of it
+        secrets.retain(|(original, _)| original.len() > 1);
+        secrets.sort_by_key(|(original, _)| std::cmp::Reverse(original.len()));
+        Scrubber::new(&secrets, (self.mac != Policy::Keep).then_some(self.mac))
+    }
+}
+
+fn redact_option(
+    value: &mut Option<String>,
+    kind: Kind,
+    policy: Policy,
+    secrets: &mut Vec<(String, String)>,
+) {
+    let Some(original) = value.as_ref() else {
+        return;
+    };
+    let Some(replacement) = kind.replace(policy, original) else {
+        return;
+    };
+    secrets.push((original.clone(), replacement.clone()));
+    *value = match policy {
+        Policy::Hide => None,
+        _ => Some(replacement),
+    };
+}
+
+/// Replaces identifying values, as whole words, and any MAC address in text
+pub struct Scrubber {
+    patterns: Vec<(Regex, String)>,
+    /// MAC address pattern and its policy, unless MACs are kept
+    macs: Option<(Regex, Policy)>,
+}
+
+impl Scrubber {
+    fn new(secrets: &[(String, String)], mac: Option<Policy>) -> Scrubber {
+        let patterns = secrets
+            .iter()
+            .filter_map(|(original, replacement)| {
+                let escaped = regex::escape(original);
+                // Whole words only, so the user `al` leaves `alsa` alone
+                let boundary = |c: Option<char>| match c {
+                    Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+                    _ => "",
+                };
+                let start = boundary(original.chars().next());
+                let end = boundary(original.chars().last());
+                let re = Regex::new(&format!("{start}{escaped}{end}")).ok()?;
+                Some((re, replacement.clone()))
+            })
+            .collect();
+        let macs = mac.map(|policy| {
+            let re = Regex::new(r"(?i)\b[0-9a-f]{2}(?:[:-][0-9a-f]{2}){5}\b").unwrap();
+            (re, policy)
+        });
+        Scrubber { patterns, macs }
+    }
+
+    /// `text` with every identifying value replaced
+    pub fn text(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (re, replacement) in &self.patterns {
+            if let Cow::Owned(replaced) = re.replace_all(&text, replacement.as_str()) {
+                text = replaced;
+            }
+        }
+        if let Some((macs, policy)) = &self.macs {
+            let replaced = macs.replace_all(&text, |c: &regex::Captures| {
+                Kind::Mac.replace(*policy, &c[0]).unwrap_or_default()
+            });
+            text = replaced.into_owned();
+        }
+        text
+    }
+
+    /// Replace the identifying values in every string of `value`
+    pub fn value(&self, value: &mut Value) {
+        let mut stack = vec![value];
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::String(s) => *s = self.text(s),
+                Value::Array(items) => stack.extend(items.iter_mut()),
+                Value::Object(map) => stack.extend(map.values_mut()),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// `neofetch` redacted as configured, or unchanged when redaction is off
+pub fn apply(neofetch: &Neofetch) -> Cow<'_, Neofetch> {
+    let redact = crate::config::current().redact;
+    match redact.enabled {
+        true => Cow::Owned(redact.redact(neofetch)),
+        false => Cow::Borrowed(neofetch),
+    }
+}
+
+/// Replacer of the identifying data of `neofetch` as configured, none when redaction is off
+pub fn scrubber(neofetch: &Neofetch) -> Option<Scrubber> {
+    let redact = crate::config::current().redact;
+    redact.enabled.then(|| redact.scrubber(neofetch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::network::NetworkInfo;
+
+    fn neofetch() -> Neofetch {
+        let mut neofetch = Neofetch::empty();
+        neofetch.hostname = Ok("workstation-7".into());
+        neofetch.user = Ok("alice".into());
+        neofetch.ip = Ok("198.51.100.7 (DE-Berlin)".into());
+        neofetch.local_ip = Ok("192.168.1.20".into());
+        neofetch.shell = Ok("/home/alice/bin/zsh".into());
+        neofetch.network = Ok(vec![
+            NetworkInfo {
+                interface_name: "lo".into(),
+                ipv4_address: Some("127.0.0.1".into()),
+                ipv6_address: Some("::1".into()),
+                mac_address: None,
+                is_up: true,
+            },
+            NetworkInfo {
+                interface_name: "eth0".into(),
+                ipv4_address: Some("192.168.1.20".into()),
+                ipv6_address: Some("fe80::1c2b:3aff:fe4d:5e6f".into()),
+                mac_address: Some("1e:2b:3a:4d:5e:6f".into()),
+                is_up: true,
+            },
+        ]);
+        neofetch
+    }
+
+    #[test]
+    fn test_redact_defaults() {
+        let redacted = Redact {
+            enabled: true,
+            ..Default::default()
+        }
+        .redact(&neofetch());
+        assert_eq!(redacted.hostname.unwrap(), "hostname");
+        assert_eq!(redacted.user.unwrap(), "user");
+        assert!(redacted.ip.is_err());
+        assert_eq!(redacted.local_ip.unwrap(), "x.x.x.x");
+        assert_eq!(redacted.shell.unwrap(), "/home/user/bin/zsh");
+        let network = redacted.network.unwrap();
+        assert_eq!(network[0].ipv4_address.as_deref(), Some("127.0.0.1"));
+        assert_eq!(network[1].ipv6_address.as_deref(), Some("x:x:x:x"));
+        assert_eq!(network[1].mac_address.as_deref(), Some("xx:xx:xx:xx:xx:xx"));
+    }
+
+    #[test]
+    fn test_redact_hash_is_consistent() {
+        let redact = Redact {
+            enabled: true,
+            hostname: Policy::Hash,
+            local_ip: Policy::Hash,
+            user: Policy::Keep,
+            ..Default::default()
+        };
+        let redacted = redact.redact(&neofetch());
+        let hostname = redacted.hostname.unwrap();
+        assert!(hostname.starts_with("host-") && hostname.len() == 13);
+        assert_eq!(redact.redact(&neofetch()).hostname.unwrap(), hostname);
+        assert_eq!(redacted.user.unwrap(), "alice");
+        let local_ip = redacted.local_ip.unwrap();
+        let network = redacted.network.unwrap();
+        assert_eq!(network[1].ipv4_address.as_deref(), Some(local_ip.as_str()));
+    }
+
+    #[test]
+    fn test_redact_config() {
+        let config = Config::parse("redact = { enabled = true, hostname = 'hash' }").unwrap();
+        assert_eq!(config.redact.hostname, Policy::Hash);
+        assert_eq!(config.redact.ip, Policy::Hide);
+        assert!(Config::parse("redact.mac = 'blur'").is_err());
+    }
+}
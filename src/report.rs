@@ -0,0 +1,406 @@
+//! Fleet report
+//!
+//! Aggregates a directory of saved snapshots into a table with one row per host,
+//! with sorting, filtering and CSV / Markdown export.
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::Neofetch;
+use crate::color::{BOLD, GREEN, RESET};
+use crate::error::{NeofetchError, Result};
+use crate::output::load;
+use crate::redact::Redact;
+
+/// Placeholder for values that are missing from a snapshot
+const MISSING: &str = "-";
+
+/// Report column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Hostname,
+    Os,
+    Kernel,
+    Cpu,
+    Memory,
+    Disk,
+    Uptime,
+    Packages,
+}
+
+impl Column {
+    /// All columns in display order
+    pub const ALL: [Column; 8] = [
+        Column::Hostname,
+        Column::Os,
+        Column::Kernel,
+        Column::Cpu,
+        Column::Memory,
+        Column::Disk,
+        Column::Uptime,
+        Column::Packages,
+    ];
+
+    /// Header label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Column::Hostname => "Hostname",
+            Column::Os => "OS",
+            Column::Kernel => "Kernel",
+            Column::Cpu => "CPU",
+            Column::Memory => "Memory",
+            Column::Disk => "Disk (/)",
+            Column::Uptime => "Uptime",
+            Column::Packages => "Packages",
+        }
+    }
+
+    /// Cell text for a host
+    pub fn value(&self, host: &Host) -> String {
+        let n = &host.neofetch;
+        let text = match self {
+            Column::Hostname => return host.name(),
+            Column::Os => n.os.as_ref().map(|o| o.to_string()).ok(),
+            Column::Kernel => n.kernel.clone().ok(),
+            Column::Cpu => n.cpu.as_ref().map(|c| c.to_string()).ok(),
+            Column::Memory => n.memory.as_ref().map(|m| m.to_string()).ok(),
+            Column::Disk => host.root_disk().map(|d| d.to_string()),
+            Column::Uptime => n.uptime.as_ref().map(|t| t.to_string()).ok(),
+            Column::Packages => n.packages.as_ref().map(|p| p.to_string()).ok(),
+        };
+        text.filter(|s| !s.is_empty())
+            .unwrap_or_else(|| MISSING.to_string())
+    }
+
+    /// Key used for sorting; numeric columns sort by their raw value
+    fn sort_key(&self, host: &Host) -> SortKey {
+        let n = &host.neofetch;
+        let number = match self {
+            Column::Memory => n.memory.as_ref().ok().map(|m| m.total as f64),
+            Column::Disk => host
+                .root_disk()
+                .filter(|d| d.total > 0)
+                .map(|d| d.used as f64 / d.total as f64),
+            Column::Uptime => n.uptime.as_ref().ok().map(|t| t.0 as f64),
+            Column::Packages => n.packages.as_ref().ok().map(|p| p.total() as f64),
+            _ => return SortKey::Text(self.value(host).to_lowercase()),
+        };
+        number.map_or(SortKey::Missing, SortKey::Number)
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hostname" | "host" => Ok(Column::Hostname),
+            "os" => Ok(Column::Os),
+            "kernel" => Ok(Column::Kernel),
+            "cpu" => Ok(Column::Cpu),
+            "memory" | "mem" => Ok(Column::Memory),
+            "disk" => Ok(Column::Disk),
+            "uptime" => Ok(Column::Uptime),
+            "packages" | "pkgs" => Ok(Column::Packages),
+            _ => Err(format!("unknown column '{s}'")),
+        }
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd)]
+enum SortKey {
+    Number(f64),
+    Text(String),
+    Missing,
+}
+
+/// Row filter in the form `column=pattern` (case-insensitive substring match)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub column: Column,
+    pub pattern: String,
+}
+
+impl Filter {
+    pub fn matches(&self, host: &Host) -> bool {
+        self.column
+            .value(host)
+            .to_lowercase()
+            .contains(&self.pattern.to_lowercase())
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (column, pattern) = s
+            .split_once('=')
+            .ok_or_else(|| format!("invalid filter '{s}' (expected column=pattern)"))?;
+        Ok(Filter {
+            column: column.trim().parse()?,
+            pattern: pattern.trim().to_string(),
+        })
+    }
+}
+
+/// Table export format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Export {
+    /// Aligned, colored table for the terminal
+    #[default]
+    Text,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Export {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Export::Text),
+            "csv" => Ok(Export::Csv),
+            "markdown" | "md" => Ok(Export::Markdown),
+            _ => Err(format!(
+                "unknown export format '{s}' (expected text, csv or markdown)"
+            )),
+        }
+    }
+}
+
+/// A snapshot loaded from the report directory
+#[derive(Debug, Clone)]
+pub struct Host {
+    pub source: PathBuf,
+    pub neofetch: Neofetch,
+}
+
+impl Host {
+    /// Hostname from the snapshot, falling back to the file name
+    pub fn name(&self) -> String {
+        match &self.neofetch.hostname {
+            Ok(h) if !h.is_empty() => h.clone(),
+            _ => self
+                .source
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| MISSING.to_string()),
+        }
+    }
+
+    /// Root filesystem, or the first disk on systems without one (Windows)
+    pub fn root_disk(&self) -> Option<&crate::disk::Disk> {
+        let disks = self.neofetch.disk.as_ref().ok()?;
+        disks.iter().find(|d| d.name == "/").or(disks.first())
+    }
+}
+
+/// Table of hosts built from a directory of snapshots
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub hosts: Vec<Host>,
+    /// Files that looked like snapshots but could not be loaded
+    pub skipped: Vec<(PathBuf, NeofetchError)>,
+}
+
+impl Report {
+    /// Load every `.json`, `.yaml`, `.yml` and `.toml` file in a directory
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Report> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| NeofetchError::file_read(dir.display().to_string(), e))?;
+
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.is_file()
+                    && matches!(
+                        p.extension().and_then(|e| e.to_str()),
+                        Some("json" | "yaml" | "yml" | "toml")
+                    )
+            })
+            .collect();
+        paths.sort();
+
+        let mut report = Report::default();
+        for path in paths {
+            match load(&path) {
+                Ok(neofetch) => report.hosts.push(Host {
+                    source: path,
+                    neofetch,
+                }),
+                Err(e) => report.skipped.push((path, e)),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Redact the identifying data of every host, when enabled
+    pub fn redact(&mut self, redact: &Redact) {
+        if redact.enabled {
+            for host in &mut self.hosts {
+                host.neofetch = redact.redact(&host.neofetch);
+            }
+        }
+    }
+
+    /// Keep only hosts matching every filter
+    pub fn filter(&mut self, filters: &[Filter]) {
+        self.hosts
+            .retain(|host| filters.iter().all(|f| f.matches(host)));
+    }
+
+    /// Sort hosts by a column; missing values always sort last
+    pub fn sort_by(&mut self, column: Column, reverse: bool) {
+        self.hosts.sort_by(|a, b| {
+            let (a, b) = (column.sort_key(a), column.sort_key(b));
+            match (&a, &b) {
+                (SortKey::Missing, SortKey::Missing) => Ordering::Equal,
+                (SortKey::Missing, _) => Ordering::Greater,
+                (_, SortKey::Missing) => Ordering::Less,
+                _ if reverse => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+                _ => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            }
+        });
+    }
+
+    fn rows(&self, columns: &[Column]) -> Vec<Vec<String>> {
+        self.hosts
+            .iter()
+            .map(|host| columns.iter().map(|c| c.value(host)).collect())
+            .collect()
+    }
+
+    /// Render in the requested export format
+    pub fn render(&self, columns: &[Column], export: Export) -> String {
+        match export {
+            Export::Text => self.to_text(columns),
+            Export::Csv => self.to_csv(columns),
+            Export::Markdown => self.to_markdown(columns),
+        }
+    }
+
+    /// Aligned table for the terminal
+    pub fn to_text(&self, columns: &[Column]) -> String {
+        let rows = self.rows(columns);
+        let widths: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                rows.iter()
+                    .map(|r| crate::utils::width(&r[i]))
+                    .chain([c.label().len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let pad = |s: &str, w: usize| format!("{s}{}", " ".repeat(w - crate::utils::width(s)));
+
+        let header: Vec<_> = columns
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| pad(c.label(), *w))
+            .collect();
+        let mut lines = vec![format!(
+            "{GREEN}{BOLD}{}{RESET}",
+            header.join("  ").trim_end()
+        )];
+        for row in rows {
+            let cells: Vec<_> = row.iter().zip(&widths).map(|(s, w)| pad(s, *w)).collect();
+            lines.push(cells.join("  ").trim_end().to_string());
+        }
+        lines.join("\n")
+    }
+
+    /// RFC 4180 CSV
+    pub fn to_csv(&self, columns: &[Column]) -> String {
+        let line = |cells: Vec<String>| {
+            cells
+                .iter()
+                .map(|s| csv_escape(s))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut lines = vec![line(
+            columns.iter().map(|c| c.label().to_string()).collect(),
+        )];
+        lines.extend(self.rows(columns).into_iter().map(line));
+        lines.join("\n")
+    }
+
+    /// GitHub-flavored Markdown table
+    pub fn to_markdown(&self, columns: &[Column]) -> String {
+        let line = |cells: Vec<String>| {
+            let cells: Vec<_> = cells.iter().map(|s| s.replace('|', "\\|")).collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut lines = vec![
+            line(columns.iter().map(|c| c.label().to_string()).collect()),
+            line(columns.iter().map(|_| "---".to_string()).collect()),
+        ];
+        lines.extend(self.rows(columns).into_iter().map(line));
+        lines.join("\n")
+    }
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        let filter: Filter = "OS = ubuntu".parse().unwrap();
+        assert_eq!(filter.column, Column::Os);
+        assert_eq!(filter.pattern, "ubuntu");
+        assert!("os".parse::<Filter>().is_err());
+        assert!("arch=x86".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(
+            csv_escape("635 (dpkg), 3 (snap)"),
+            "\"635 (dpkg), 3 (snap)\""
+        );
+        assert_eq!(csv_escape("a \"b\""), "\"a \"\"b\"\"\"");
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut neofetch = Neofetch::empty();
+        neofetch.hostname = Ok("db-primary".into());
+        let mut report = Report {
+            hosts: vec![Host {
+                source: "db-primary.json".into(),
+                neofetch,
+            }],
+            skipped: Vec::new(),
+        };
+        report.redact(&Redact {
+            enabled: true,
+            ..Default::default()
+        });
+        let text = report.to_csv(&[Column::Hostname]);
+        assert_eq!(text.lines().nth(1), Some("hostname"));
+    }
+}
@@ -0,0 +1,352 @@
+//! Rhai scripts defined in the configuration
+//!
+//! Each `[[script]]` entry is compiled at startup and run on the collected data
+//! whenever it is rendered. Scripts see every module in the `info` map (errors
+//! as `#{ error: ... }`), can rewrite or hide a module by assigning to it, and
+//! their return value (if any) becomes a line of its own under the entry's name:
+//!
+//! ```rhai
+//! // Kernel as major.minor
+//! let v = info.kernel.split(".");
+//! info.kernel = `${v[0]}.${v[1]}`;
+//!
+//! // Hottest GPU sensor, only when above 70°C
+//! let hot = info.temperature.filter(|s| s.label.contains("gpu") && s.temperature_celsius > 70.0);
+//! if hot.len() > 0 { `${RED}${hot[0].temperature_celsius}°C${RESET}` }
+//! ```
+//!
+//! Helpers: `read_file(path)`, `exec(command)` (run through the shell, with the
+//! timeout of custom commands) and the color constants of [`crate::color`]
+//! (`RED`, `BOLD`, `RESET`, ...).
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use rhai::{AST, Dynamic, Engine, EvalAltResult, Scope};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use crate::Neofetch;
+use crate::collector::Collector;
+use crate::color::*;
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+
+/// Operation budget of one script run, so a runaway loop cannot hang the output
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A `[[script]]` entry of the configuration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptModule {
+    /// Module name of the line produced by the script's return value
+    pub name: String,
+    /// Label of the line, by default the name in title case
+    pub label: Option<String>,
+    /// Script file, relative to the configuration file
+    pub file: Option<PathBuf>,
+    /// Inline script
+    pub code: Option<String>,
+}
+
+struct Script {
+    name: &'static str,
+    ast: AST,
+}
+
+struct Scripts {
+    engine: Engine,
+    scripts: Vec<Script>,
+}
+
+static SCRIPTS: RwLock<Option<Arc<Scripts>>> = RwLock::new(None);
+
+/// Placeholder collector so script lines are placed, labeled and serialized like modules
+struct ScriptLine {
+    name: &'static str,
+    label: Option<String>,
+}
+
+impl Collector for ScriptLine {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn label(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => crate::collector::title_case(self.name),
+        }
+    }
+
+    fn collect(&self) -> BoxFuture<'_, Result<Value>> {
+        Box::pin(async { Err(NeofetchError::data_unavailable("set when rendering")) })
+    }
+}
+
+/// Run `command` through the shell, giving up after `timeout`
+///
+/// Scripts run while rendering, which may be on an async worker: the worker
+/// hands its other tasks off while the command runs, so the runtime keeps going.
+fn exec(command: &str, timeout: Duration) -> Result<String> {
+    let run = crate::custom::shell(command, timeout);
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(run))
+        }
+        // Outside a runtime, or on one with a single thread that must not block
+        _ => std::thread::scope(|s| {
+            s.spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?
+                    .block_on(run)
+            })
+            .join()
+            .unwrap_or_else(|_| Err(NeofetchError::system_call("exec panicked")))
+        }),
+    }
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.register_fn(
+        "read_file",
+        |path: &str| -> std::result::Result<String, Box<EvalAltResult>> {
+            crate::utils::read_file_to_string_sync(path).map_err(|e| e.to_string().into())
+        },
+    );
+    engine.register_fn(
+        "exec",
+        |command: &str| -> std::result::Result<String, Box<EvalAltResult>> {
+            exec(command, crate::custom::DEFAULT_TIMEOUT).map_err(|e| e.to_string().into())
+        },
+    );
+    engine
+}
+
+fn scope() -> Scope<'static> {
+    let mut scope = Scope::new();
+    let colors = [
+        ("BLACK", BLACK),
+        ("RED", RED),
+        ("GREEN", GREEN),
+        ("YELLOW", YELLOW),
+        ("BLUE", BLUE),
+        ("MAGENTA", MAGENTA),
+        ("CYAN", CYAN),
+        ("WHITE", WHITE),
+        ("BRIGHT_BLACK", BRIGHT_BLACK),
+        ("BRIGHT_RED", BRIGHT_RED),
+        ("BRIGHT_GREEN", BRIGHT_GREEN),
+        ("BRIGHT_YELLOW", BRIGHT_YELLOW),
+        ("BRIGHT_BLUE", BRIGHT_BLUE),
+        ("BRIGHT_MAGENTA", BRIGHT_MAGENTA),
+        ("BRIGHT_CYAN", BRIGHT_CYAN),
+        ("BRIGHT_WHITE", BRIGHT_WHITE),
+        ("BOLD", BOLD),
+        ("RESET", RESET),
+    ];
+    for (name, code) in colors {
+        scope.push_constant(name, code);
+    }
+    scope
+}
+
+/// Compile the scripts and register their lines
+///
+/// `base` is the directory relative script files are resolved against.
+pub(crate) fn load(modules: &[ScriptModule], base: Option<&std::path::Path>) -> Result<()> {
+    let engine = engine();
+    let mut scripts = Vec::new();
+    for module in modules {
+        let error =
+            |reason: String| NeofetchError::config(format!("script '{}': {reason}", module.name));
+        let code = match (&module.code, &module.file) {
+            (Some(code), None) => code.clone(),
+            (None, Some(file)) => {
+                let path = match base {
+                    Some(base) => base.join(file),
+                    None => file.clone(),
+                };
+                crate::utils::read_file_to_string_sync(path).map_err(|e| error(e.to_string()))?
+            }
+            _ => return Err(error("set exactly one of `code` or `file`".into())),
+        };
+        let ast = engine.compile(&code).map_err(|e| error(e.to_string()))?;
+        // Registered lines live for the whole run
+        let name: &'static str = Box::leak(module.name.clone().into_boxed_str());
+        crate::collector::register(ScriptLine {
+            name,
+            label: module.label.clone(),
+        })?;
+        scripts.push(Script { name, ast });
+    }
+    let mut current = SCRIPTS
+        .write()
+        .map_err(|e| NeofetchError::system_call(e.to_string()))?;
+    *current = Some(Arc::new(Scripts { engine, scripts }));
+    Ok(())
+}
+
+fn to_json(value: Dynamic) -> Value {
+    rhai::serde::from_dynamic(&value).unwrap_or(Value::Null)
+}
+
+impl Scripts {
+    /// Run one script on `neofetch`, applying its rewrites and storing its line
+    fn run(&self, script: &Script, neofetch: &mut Neofetch) {
+        let modules = Module::all();
+        let entries: serde_json::Map<String, Value> = modules
+            .iter()
+            .map(|m| (m.name().to_string(), neofetch.entry(*m)))
+            .collect();
+        let info = rhai::serde::to_dynamic(&entries).unwrap_or_default();
+
+        let mut scope = scope();
+        scope.push("info", info);
+        let result = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &script.ast);
+
+        let line = match result {
+            Ok(value) if value.is_unit() => {
+                Err(NeofetchError::data_unavailable("script returned nothing"))
+            }
+            Ok(value) => Ok(to_json(value)),
+            Err(e) => {
+                neofetch
+                    .extra
+                    .set(script.name, Err(NeofetchError::script(e.to_string())));
+                return;
+            }
+        };
+        if let Some(info) = scope.get_value::<rhai::Map>("info") {
+            for module in modules {
+                let Some(value) = info.get(module.name()).cloned().map(to_json) else {
+                    continue;
+                };
+                if entries.get(module.name()) != Some(&value) {
+                    let value = match value {
+                        Value::Null => Err(NeofetchError::data_unavailable("hidden by script")),
+                        v => Ok(v),
+                    };
+                    neofetch.set_value(module, value);
+                }
+            }
+        }
+        neofetch.extra.set(script.name, line);
+    }
+}
+
+fn current() -> Option<Arc<Scripts>> {
+    SCRIPTS.read().ok().and_then(|s| s.clone())
+}
+
+/// Names of the configured scripts, in the order they run
+pub fn names() -> Vec<&'static str> {
+    current()
+        .map(|s| s.scripts.iter().map(|script| script.name).collect())
+        .unwrap_or_default()
+}
+
+/// Run the script called `name` on `neofetch`
+pub fn run(name: &str, neofetch: &mut Neofetch) {
+    if let Some(scripts) = current()
+        && let Some(script) = scripts.scripts.iter().find(|s| s.name == name)
+    {
+        scripts.run(script, neofetch);
+    }
+}
+
+/// `neofetch` with the configured scripts applied, or unchanged when there are none
+pub fn apply(neofetch: &Neofetch) -> Cow<'_, Neofetch> {
+    let Some(scripts) = current().filter(|s| !s.scripts.is_empty()) else {
+        return Cow::Borrowed(neofetch);
+    };
+    let mut neofetch = neofetch.clone();
+    for script in &scripts.scripts {
+        scripts.run(script, &mut neofetch);
+    }
+    Cow::Owned(neofetch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_rewrites_and_lines() {
+        let engine = engine();
+        let scripts = Scripts {
+            scripts: vec![Script {
+                name: "kernel_short",
+                ast: engine
+                    .compile(
+                        r#"
+                        let v = info.kernel.split(".");
+                        info.kernel = `${v[0]}.${v[1]}`;
+                        info.shell = ();
+                        `${GREEN}${v.len()} parts${RESET}`
+                        "#,
+                    )
+                    .unwrap(),
+            }],
+            engine,
+        };
+
+        let mut neofetch = Neofetch::empty();
+        neofetch.kernel = Ok("6.8.0-45-generic".into());
+        neofetch.shell = Ok("bash".into());
+        scripts.run(&scripts.scripts[0], &mut neofetch);
+
+        assert_eq!(neofetch.kernel.unwrap(), "6.8");
+        assert!(neofetch.shell.is_err());
+        let line = neofetch
+            .extra
+            .get("kernel_short")
+            .unwrap()
+            .as_ref()
+            .unwrap();
+        assert_eq!(line, &Value::String(format!("{GREEN}3 parts{RESET}")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exec_timeout() {
+        let timeout = Duration::from_millis(50);
+        let fast = exec("echo hi", crate::custom::DEFAULT_TIMEOUT);
+        assert_eq!(fast.unwrap(), "hi");
+        let slow = exec("sleep 5", timeout);
+        assert!(matches!(slow, Err(NeofetchError::Timeout(_))));
+
+        // On an async worker, as when rendering in watch or serve mode
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let slow = runtime.block_on(async { exec("sleep 5", timeout) });
+        assert!(matches!(slow, Err(NeofetchError::Timeout(_))));
+    }
+
+    #[test]
+    fn test_script_error() {
+        let engine = engine();
+        let script = Script {
+            name: "broken",
+            ast: engine.compile("info.nope.len()").unwrap(),
+        };
+        let scripts = Scripts {
+            scripts: Vec::new(),
+            engine,
+        };
+        let mut neofetch = Neofetch::empty();
+        scripts.run(&script, &mut neofetch);
+        assert!(matches!(
+            neofetch.extra.get("broken"),
+            Some(Err(NeofetchError::Script(_)))
+        ));
+    }
+}
@@ -0,0 +1,370 @@
+//! Local HTTP server
+//!
+//! Serves the live system over plain HTTP/1.1 so dashboards can poll hosts
+//! without SSH:
+//!
+//! - `/` rendered HTML
+//! - `/json` full snapshot
+//! - `/metrics` Prometheus text exposition
+//! - `/module/<name>` a single module, e.g. `/module/memory`
+//!
+//! Collected results are cached for a configurable interval, so frequent
+//! scrapes don't rerun slow collectors like `get_packages()` and `get_gpu()`.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+use crate::Neofetch;
+use crate::error::{NeofetchError, Result};
+use crate::module::Module;
+
+/// Default listen address, local connections only
+pub const DEFAULT_BIND: &str = "127.0.0.1:8686";
+
+/// Largest request head accepted
+const MAX_REQUEST: usize = 8192;
+
+/// Time allowed for a client to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Collected snapshot shared between requests
+pub struct Cache {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, Arc<Neofetch>)>>,
+}
+
+impl Cache {
+    pub fn new(ttl: Duration) -> Self {
+        Cache {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Cached snapshot, collecting a new one once it is older than the TTL
+    pub async fn get(&self) -> Arc<Neofetch> {
+        let mut entry = self.entry.lock().await;
+        if let Some((at, neofetch)) = entry.as_ref()
+            && at.elapsed() < self.ttl
+        {
+            return neofetch.clone();
+        }
+        let neofetch = Neofetch::new().await;
+        let neofetch = Arc::new(crate::output::prepare(&neofetch).into_owned());
+        *entry = Some((Instant::now(), neofetch.clone()));
+        neofetch
+    }
+}
+
+/// Endpoint served by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Html,
+    Json,
+    Metrics,
+    Module(Module),
+}
+
+impl Route {
+    /// Route for a request target, ignoring any query string
+    pub fn parse(target: &str) -> Option<Route> {
+        let path = target.split(['?', '#']).next().unwrap_or_default();
+        match path.trim_end_matches('/') {
+            "" => Some(Route::Html),
+            "/json" => Some(Route::Json),
+            "/metrics" => Some(Route::Metrics),
+            p => p.strip_prefix("/module/")?.parse().ok().map(Route::Module),
+        }
+    }
+
+    fn render(&self, neofetch: &Neofetch) -> Result<Response> {
+        Ok(match self {
+            Route::Html => {
+                Response::new(200, "text/html; charset=utf-8", crate::html::page(neofetch))
+            }
+            Route::Json => {
+                Response::new(200, "application/json", crate::output::to_json(neofetch)?)
+            }
+            Route::Metrics => Response::new(
+                200,
+                "text/plain; version=0.0.4; charset=utf-8",
+                metrics(neofetch),
+            ),
+            Route::Module(module) => {
+                let body = serde_json::to_string_pretty(&neofetch.entry(*module))
+                    .map_err(|e| NeofetchError::serialization(e.to_string()))?;
+                Response::new(200, "application/json", body)
+            }
+        })
+    }
+}
+
+/// HTTP response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &'static str, body: impl Into<String>) -> Self {
+        Response {
+            status,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Response::new(status, "text/plain; charset=utf-8", body)
+    }
+
+    fn to_bytes(&self, head_only: bool) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Internal Server Error",
+        };
+        let mut bytes = format!(
+            "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        )
+        .into_bytes();
+        if !head_only {
+            bytes.extend_from_slice(self.body.as_bytes());
+        }
+        bytes
+    }
+}
+
+/// Answer a GET request for `target`
+pub async fn respond(target: &str, cache: &Cache) -> Response {
+    let Some(route) = Route::parse(target) else {
+        return Response::text(404, "not found\n");
+    };
+    route
+        .render(&*cache.get().await)
+        .unwrap_or_else(|e| Response::text(500, format!("{e}\n")))
+}
+
+/// Prometheus label value escaping
+fn escape_label(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Prometheus text exposition builder
+#[derive(Default)]
+struct Metrics(String);
+
+impl Metrics {
+    fn family(&mut self, name: &str, help: &str) {
+        let _ = writeln!(self.0, "# HELP neofetch_{name} {help}");
+        let _ = writeln!(self.0, "# TYPE neofetch_{name} gauge");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl std::fmt::Display) {
+        let labels: Vec<_> = labels
+            .iter()
+            .map(|(k, v)| format!("{k}=\"{}\"", escape_label(v)))
+            .collect();
+        if labels.is_empty() {
+            let _ = writeln!(self.0, "neofetch_{name} {value}");
+        } else {
+            let _ = writeln!(self.0, "neofetch_{name}{{{}}} {value}", labels.join(","));
+        }
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: impl std::fmt::Display) {
+        self.family(name, help);
+        self.sample(name, &[], value);
+    }
+}
+
+/// Render the numeric fields in the Prometheus text format
+pub fn metrics(neofetch: &Neofetch) -> String {
+    let mut m = Metrics::default();
+
+    m.family("module_up", "Whether the module was collected successfully");
+    for module in Module::all() {
+        let up = u8::from(neofetch.value(module).is_ok());
+        m.sample("module_up", &[("module", module.name())], up);
+    }
+
+    let text = |r: &Result<String>| r.clone().unwrap_or_default();
+    let os = neofetch
+        .os
+        .as_ref()
+        .map(|o| o.to_string())
+        .unwrap_or_default();
+    let cpu = neofetch
+        .cpu
+        .as_ref()
+        .map(|c| c.name.clone())
+        .unwrap_or_default();
+    m.family("info", "System description");
+    m.sample(
+        "info",
+        &[
+            ("hostname", &text(&neofetch.hostname)),
+            ("os", &os),
+            ("kernel", &text(&neofetch.kernel)),
+            ("cpu", &cpu),
+        ],
+        1,
+    );
+
+    if let Ok(uptime) = &neofetch.uptime {
+        m.gauge("uptime_seconds", "System uptime", uptime.0);
+    }
+
+    if let Ok(load) = &neofetch.load_average {
+        m.gauge("load1", "Load average over 1 minute", load.one_min);
+        m.gauge("load5", "Load average over 5 minutes", load.five_min);
+        m.gauge("load15", "Load average over 15 minutes", load.fifteen_min);
+    }
+
+    if let Ok(cpu) = &neofetch.cpu {
+        m.gauge("cpu_cores", "Number of CPU cores", cpu.cores);
+        m.gauge("cpu_speed_mhz", "CPU speed", cpu.speed);
+    }
+
+    if let Ok(memory) = &neofetch.memory {
+        m.gauge("memory_total_bytes", "Total physical memory", memory.total);
+        m.gauge("memory_used_bytes", "Used physical memory", memory.used);
+    }
+
+    if let Ok(disks) = &neofetch.disk {
+        m.family("disk_total_bytes", "Total disk space");
+        for disk in disks {
+            m.sample("disk_total_bytes", &[("mount", &disk.name)], disk.total);
+        }
+        m.family("disk_used_bytes", "Used disk space");
+        for disk in disks {
+            m.sample("disk_used_bytes", &[("mount", &disk.name)], disk.used);
+        }
+    }
+
+    if let Ok(gpus) = &neofetch.gpu {
+        m.family("gpu_memory_bytes", "GPU memory");
+        for gpu in gpus {
+            m.sample("gpu_memory_bytes", &[("name", &gpu.name)], gpu.ram);
+        }
+    }
+
+    if let Ok(sensors) = &neofetch.temperature {
+        m.family("temperature_celsius", "Sensor temperature");
+        for sensor in sensors {
+            m.sample(
+                "temperature_celsius",
+                &[("sensor", &sensor.label)],
+                sensor.temperature_celsius,
+            );
+        }
+    }
+
+    if let Ok(battery) = &neofetch.battery {
+        m.gauge("battery_percent", "Battery charge", battery);
+    }
+
+    if let Ok(packages) = &neofetch.packages {
+        m.family("packages", "Installed packages per package manager");
+        for (manager, count) in packages.counts() {
+            m.sample("packages", &[("manager", manager)], count);
+        }
+    }
+
+    m.0
+}
+
+async fn handle(mut stream: TcpStream, cache: &Cache) -> std::io::Result<()> {
+    let mut buf = vec![0; MAX_REQUEST];
+    let mut len = 0;
+    let read = async {
+        while len < buf.len() && !buf[..len].windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf[len..]).await?;
+            if n == 0 {
+                break;
+            }
+            len += n;
+        }
+        std::io::Result::Ok(())
+    };
+    if tokio::time::timeout(READ_TIMEOUT, read).await.is_err() {
+        return Ok(());
+    }
+
+    let head = String::from_utf8_lossy(&buf[..len]);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let (method, target) = (request_line.next(), request_line.next());
+    let response = match (method, target) {
+        (Some("GET" | "HEAD"), Some(target)) => respond(target, cache).await,
+        (Some(_), Some(_)) => Response::text(405, "method not allowed\n"),
+        _ => Response::text(400, "bad request\n"),
+    };
+
+    stream
+        .write_all(&response.to_bytes(method == Some("HEAD")))
+        .await?;
+    stream.shutdown().await
+}
+
+/// Serve until the process is stopped
+pub async fn serve(bind: &str, ttl: Duration) -> Result<()> {
+    let listener = TcpListener::bind(bind)
+        .await
+        .map_err(|e| NeofetchError::Io(format!("cannot bind {bind}: {e}")))?;
+    eprintln!("listening on http://{}", listener.local_addr()?);
+
+    let cache = Arc::new(Cache::new(ttl));
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let cache = cache.clone();
+        tokio::spawn(async move {
+            let _ = handle(stream, &cache).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_parse() {
+        assert_eq!(Route::parse("/"), Some(Route::Html));
+        assert_eq!(Route::parse("/json?pretty"), Some(Route::Json));
+        assert_eq!(Route::parse("/metrics/"), Some(Route::Metrics));
+        assert_eq!(
+            Route::parse("/module/wm_theme"),
+            Some(Route::Module(Module::WmTheme))
+        );
+        assert_eq!(Route::parse("/module/bogus"), None);
+        assert_eq!(Route::parse("/other"), None);
+    }
+
+    #[test]
+    fn test_metrics_sample() {
+        let mut m = Metrics::default();
+        m.gauge("battery_percent", "Battery charge", 80);
+        m.sample("disk_used_bytes", &[("mount", "C:\\ \"x\"")], 1);
+        assert_eq!(
+            m.0,
+            "# HELP neofetch_battery_percent Battery charge\n\
+             # TYPE neofetch_battery_percent gauge\n\
+             neofetch_battery_percent 80\n\
+             neofetch_disk_used_bytes{mount=\"C:\\\\ \\\"x\\\"\"} 1\n"
+        );
+    }
+}
@@ -36,9 +36,7 @@ pub fn get_file_name(path: &str) -> Result<String> {
 
 pub fn get_pid_name(id: u32) -> Result<String> {
     let path = format!("/proc/{id}/comm");
-    std::fs::read_to_string(&path)
-        .map(|i| i.trim().to_string())
-        .map_err(|e| NeofetchError::file_read(path, e))
+    crate::utils::read_file_to_string_sync(path).map(|i| i.trim().to_string())
 }
 
 pub fn get_ppid(id: u32) -> Result<u32> {
@@ -3,10 +3,11 @@
 //! Collects various system statistics like process count, load average, and boot time.
 
 use crate::error::{NeofetchError, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// System load average
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct LoadAverage {
     /// 1-minute load average
     pub one_min: f32,
@@ -35,8 +36,7 @@ pub async fn get_process_count() -> Result<usize> {
         return Err(NeofetchError::data_unavailable("/proc not available"));
     }
 
-    let entries = std::fs::read_dir(proc_path)
-        .map_err(|e| NeofetchError::file_read("/proc".to_string(), e))?;
+    let entries = crate::utils::file::read_dir(proc_path)?;
 
     let count = entries
         .filter_map(|e| e.ok())
@@ -74,7 +74,7 @@ pub async fn get_process_count() -> Result<usize> {
 }
 
 /// Get load average on Unix-like systems
-#[cfg(unix)]
+#[cfg(all(unix, not(target_os = "macos")))]
 pub async fn get_load_average() -> Result<LoadAverage> {
     use crate::utils::read_file_to_string;
 
@@ -108,6 +108,22 @@ pub async fn get_load_average() -> Result<LoadAverage> {
     })
 }
 
+/// Get load average on macOS (no /proc/loadavg)
+#[cfg(target_os = "macos")]
+pub async fn get_load_average() -> Result<LoadAverage> {
+    let mut loads = [0f64; 3];
+    let result = unsafe { libc::getloadavg(loads.as_mut_ptr(), 3) };
+    if result != 3 {
+        return Err(NeofetchError::system_call("getloadavg failed"));
+    }
+
+    Ok(LoadAverage {
+        one_min: loads[0] as f32,
+        five_min: loads[1] as f32,
+        fifteen_min: loads[2] as f32,
+    })
+}
+
 /// Get load average on Windows (not supported)
 #[cfg(windows)]
 pub async fn get_load_average() -> Result<LoadAverage> {
@@ -117,13 +133,14 @@ pub async fn get_load_average() -> Result<LoadAverage> {
 /// Get system boot time as Unix timestamp
 #[cfg(unix)]
 pub async fn get_boot_time() -> Result<i64> {
-    use crate::utils::{parse_proc_file, read_file_to_string};
+    use crate::utils::read_file_to_string;
 
     let content = read_file_to_string("/proc/stat").await?;
-    let stat_info = parse_proc_file(&content);
 
-    let btime_str = stat_info
-        .get("btime")
+    // Unlike /proc/meminfo, /proc/stat separates keys with spaces: `btime 1700000000`
+    let btime_str = content
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))
         .ok_or_else(|| NeofetchError::data_unavailable("btime not found in /proc/stat"))?;
 
     btime_str
@@ -231,7 +248,7 @@ pub async fn get_thread_count() -> Result<usize> {
 
     let mut total_threads = 0;
 
-    if let Ok(entries) = std::fs::read_dir(proc_path) {
+    if let Ok(entries) = crate::utils::file::read_dir(proc_path) {
         for entry in entries.flatten() {
             let file_name = entry.file_name();
             let name_str = file_name.to_string_lossy();
@@ -239,8 +256,8 @@ pub async fn get_thread_count() -> Result<usize> {
             // Check if it's a process directory (numeric)
             if name_str.chars().all(|c| c.is_ascii_digit()) {
                 let task_dir = entry.path().join("task");
-                if let Ok(task_entries) = std::fs::read_dir(task_dir) {
-                    total_threads += task_entries.count();
+                if let Ok(count) = crate::utils::file::count_dir_entries(task_dir) {
+                    total_threads += count;
                 }
             }
         }
@@ -3,10 +3,11 @@
 //! Collects temperature information from various system sensors.
 
 use crate::error::{NeofetchError, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// Temperature sensor information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TempSensor {
     /// Sensor label/name
     pub label: String,
@@ -16,7 +17,8 @@ pub struct TempSensor {
 
 impl Display for TempSensor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}: {:.1}°C", self.label, self.temperature_celsius)
+        let temperature = crate::units::current().temperature(self.temperature_celsius as f64);
+        write!(f, "{}: {temperature}", self.label)
     }
 }
 
@@ -211,6 +213,7 @@ pub async fn get_temperature_sensors() -> Result<Vec<TempSensor>> {
 /// Read hwmon temperature sensors (Linux)
 #[cfg(target_os = "linux")]
 async fn read_hwmon_sensors() -> Result<Vec<TempSensor>> {
+    use crate::utils::file::read_dir;
     use crate::utils::read_file_to_string;
 
     let hwmon_path = std::path::Path::new("/sys/class/hwmon");
@@ -220,12 +223,12 @@ async fn read_hwmon_sensors() -> Result<Vec<TempSensor>> {
 
     let mut sensors = Vec::new();
 
-    if let Ok(entries) = std::fs::read_dir(hwmon_path) {
+    if let Ok(entries) = read_dir(hwmon_path) {
         for entry in entries.flatten() {
             let hwmon_dir = entry.path();
 
             // Try to find temp*_input files
-            if let Ok(temp_entries) = std::fs::read_dir(&hwmon_dir) {
+            if let Ok(temp_entries) = read_dir(&hwmon_dir) {
                 for temp_entry in temp_entries.flatten() {
                     let filename = temp_entry.file_name();
                     let filename_str = filename.to_string_lossy();
@@ -273,7 +276,7 @@ pub async fn get_temperature_sensors() -> Result<Vec<TempSensor>> {
 
     let mut collector = TempCollector::new();
 
-    if let Ok(entries) = std::fs::read_dir(thermal_path) {
+    if let Ok(entries) = crate::utils::file::read_dir(thermal_path) {
         for entry in entries.flatten() {
             let zone_path = entry.path();
             let zone_name = entry.file_name();
@@ -0,0 +1,511 @@
+//! Per-module format templates
+//!
+//! A template such as `"{name} ({cores}) @ {ghz:.1} GHz"` replaces a module's
+//! default text. Placeholders name a structured field (as in the JSON output)
+//! or one of the derived fields below, optionally followed by a format spec:
+//! `<`, `>` or `^` alignment, a width and a `.precision` for numbers.
+//! `{value}` is always the default text, and `{{` / `}}` are literal braces.
+//!
+//! | module                     | derived fields                                     |
+//! |----------------------------|----------------------------------------------------|
+//! | `cpu`                      | `ghz`, `max_ghz`, `speed` (in the configured unit) |
+//! | `memory`, `disk`           | `used`, `total`, `free` (human readable), `*_gib`, `*_mib`, `percent` |
+//! | `gpu`                      | `ram`, `ram_gib`                                   |
+//! | `temperature`              | `temperature`, `unit` (in the configured scale)    |
+//! | `uptime`                   | `seconds`, `days`, `hours`, `minutes`              |
+//! | `display`                  | `width`, `height`                                  |
+//! | `packages`                 | `total`                                            |
+//! | `battery`                  | `percent`                                          |
+//! | `wm`                       | `theme`                                            |
+
+use std::str::FromStr;
+
+use serde_json::{Map, Value, json};
+
+use crate::module::Module;
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+const MIB: f64 = 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Field {
+        name: String,
+        align: Option<Align>,
+        width: Option<usize>,
+        precision: Option<usize>,
+    },
+}
+
+/// Parsed format template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+fn parse_field(field: &str) -> Result<Part, String> {
+    let (name, spec) = field.split_once(':').unwrap_or((field, ""));
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("invalid field name '{{{field}}}'"));
+    }
+
+    let (align, spec) = match spec.chars().next() {
+        Some('<') => (Some(Align::Left), &spec[1..]),
+        Some('>') => (Some(Align::Right), &spec[1..]),
+        Some('^') => (Some(Align::Center), &spec[1..]),
+        _ => (None, spec),
+    };
+    let (width, precision) = spec.split_once('.').unwrap_or((spec, ""));
+    let number = |s: &str| -> Result<Option<usize>, String> {
+        if s.is_empty() {
+            return Ok(None);
+        }
+        s.parse()
+            .map(Some)
+            .map_err(|_| format!("invalid format spec in '{{{field}}}'"))
+    };
+    let precision = match spec.contains('.') {
+        true if precision.is_empty() => {
+            return Err(format!("missing precision in '{{{field}}}'"));
+        }
+        _ => number(precision)?,
+    };
+    Ok(Part::Field {
+        name: name.to_string(),
+        align,
+        width: number(width)?,
+        precision,
+    })
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => return Err(format!("unclosed '{{' in \"{s}\"")),
+                        }
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(parse_field(&field)?);
+                }
+                '}' => return Err(format!("unmatched '}}' in \"{s}\" (use '}}}}')")),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(Template { parts })
+    }
+}
+
+fn pad(s: String, align: Option<Align>, width: Option<usize>) -> String {
+    let Some(width) = width else {
+        return s;
+    };
+    let fill = width.saturating_sub(s.chars().count());
+    let (left, right) = match align {
+        Some(Align::Right) => (fill, 0),
+        Some(Align::Center) => (fill / 2, fill - fill / 2),
+        // Like `format!`, numbers align right and text left by default
+        None if s.parse::<f64>().is_ok() => (fill, 0),
+        _ => (0, fill),
+    };
+    format!("{}{s}{}", " ".repeat(left), " ".repeat(right))
+}
+
+fn text(value: &Value, precision: Option<usize>) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match (precision, n.as_f64()) {
+            (Some(p), Some(f)) => crate::i18n::current().decimal(format!("{f:.p$}")),
+            // Floats come from f32 fields; print them without f64 noise
+            _ if n.is_f64() => {
+                let f = n.as_f64().unwrap_or_default() as f32;
+                crate::i18n::current().decimal(f.to_string())
+            }
+            _ => n.to_string(),
+        },
+        Value::Array(items) => items
+            .iter()
+            .map(|v| text(v, precision))
+            .collect::<Vec<_>>()
+            .join("x"),
+        v => v.to_string(),
+    }
+}
+
+impl Template {
+    /// Names of the fields used by the template
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|p| match p {
+            Part::Field { name, .. } => Some(name.as_str()),
+            Part::Text(_) => None,
+        })
+    }
+
+    /// Fill in the fields from `context`; unknown fields render empty
+    pub fn render(&self, context: &Map<String, Value>) -> String {
+        let mut s = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(t) => s.push_str(t),
+                Part::Field {
+                    name,
+                    align,
+                    width,
+                    precision,
+                } => {
+                    let value = context.get(name).map(|v| text(v, *precision));
+                    s.push_str(&pad(value.unwrap_or_default(), *align, *width));
+                }
+            }
+        }
+        s
+    }
+}
+
+/// Fields a template of `module` may use; `None` when they are only known at runtime
+pub fn fields(module: Module) -> Option<&'static [&'static str]> {
+    const BYTES: [&str; 12] = [
+        "total_bytes",
+        "used_bytes",
+        "total",
+        "used",
+        "free",
+        "total_gib",
+        "used_gib",
+        "free_gib",
+        "total_mib",
+        "used_mib",
+        "free_mib",
+        "percent",
+    ];
+    Some(match module {
+        Module::Os => &["distro", "name", "arch"],
+        Module::Wm => &["name", "theme"],
+        Module::Uptime => &["seconds", "days", "hours", "minutes"],
+        Module::LoadAverage => &["one_min", "five_min", "fifteen_min"],
+        Module::Packages => &["dpkg", "snap", "pacman", "scoop", "opkg", "total"],
+        Module::Display => &[
+            "name",
+            "friendly_name",
+            "refresh_rate_hz",
+            "external",
+            "resolution",
+            "scale_resolution",
+            "rotation",
+            "primary",
+            "width",
+            "height",
+        ],
+        Module::Memory => &BYTES,
+        Module::Disk => &[
+            "name",
+            "total_bytes",
+            "used_bytes",
+            "total",
+            "used",
+            "free",
+            "total_gib",
+            "used_gib",
+            "free_gib",
+            "total_mib",
+            "used_mib",
+            "free_mib",
+            "percent",
+        ],
+        Module::Cpu => &[
+            "name",
+            "cores",
+            "threads",
+            "speed_mhz",
+            "max_speed_mhz",
+            "ghz",
+            "max_ghz",
+            "speed",
+        ],
+        Module::Gpu => &["name", "version", "ram_bytes", "ram", "ram_gib"],
+        Module::Battery => &["percent"],
+        Module::Temperature => &["label", "temperature_celsius", "temperature", "unit"],
+        Module::Network => &[
+            "interface_name",
+            "ipv4_address",
+            "ipv6_address",
+            "mac_address",
+            "is_up",
+        ],
+        Module::Extra(_) => return None,
+        _ => &[],
+    })
+}
+
+/// Check that a template only uses fields available for `module`
+pub fn validate(module: Module, template: &Template) -> Result<(), String> {
+    let Some(allowed) = fields(module) else {
+        return Ok(());
+    };
+    match template
+        .fields()
+        .find(|f| *f != "value" && !allowed.contains(f))
+    {
+        Some(field) => {
+            let mut available = vec!["value"];
+            available.extend(allowed);
+            Err(format!(
+                "unknown field '{field}' for {module} (available: {})",
+                available.join(", ")
+            ))
+        }
+        None => Ok(()),
+    }
+}
+
+fn number(map: &Map<String, Value>, key: &str) -> f64 {
+    map.get(key).and_then(Value::as_f64).unwrap_or_default()
+}
+
+/// Human readable, GiB, MiB and percent fields of a used/total pair
+fn bytes(map: &mut Map<String, Value>) {
+    let total = number(map, "total_bytes");
+    let used = number(map, "used_bytes");
+    let free = (total - used).max(0.0);
+    let units = crate::units::current();
+    for (name, n) in [("total", total), ("used", used), ("free", free)] {
+        map.insert(name.into(), json!(units.bytes(n)));
+        map.insert(format!("{name}_gib"), json!(n / GIB));
+        map.insert(format!("{name}_mib"), json!(n / MIB));
+    }
+    let percent = if total > 0.0 {
+        used / total * 100.0
+    } else {
+        0.0
+    };
+    map.insert("percent".into(), json!(percent));
+}
+
+/// Template fields of one item: its structured fields, the derived ones and `value`
+pub fn context(module: Module, item: Value, default: String) -> Map<String, Value> {
+    let mut map = match item {
+        Value::Object(map) => map,
+        v => {
+            let mut map = Map::new();
+            match module {
+                Module::Uptime => {
+                    let s = v.as_u64().unwrap_or_default();
+                    map.insert("seconds".into(), json!(s));
+                    map.insert("days".into(), json!(s / 86400));
+                    map.insert("hours".into(), json!(s / 3600 % 24));
+                    map.insert("minutes".into(), json!(s / 60 % 60));
+                }
+                Module::Battery => {
+                    map.insert("percent".into(), v);
+                }
+                _ => {}
+            }
+            map
+        }
+    };
+    match module {
+        Module::Cpu => {
+            let ghz = number(&map, "speed_mhz") / 1000.0;
+            // Fall back to the current speed where the maximum is not reported
+            let max_ghz = match number(&map, "max_speed_mhz") {
+                0.0 => ghz,
+                mhz => mhz / 1000.0,
+            };
+            let speed = crate::units::current().frequency(ghz * 1000.0);
+            map.insert("ghz".into(), json!(ghz));
+            map.insert("max_ghz".into(), json!(max_ghz));
+            map.insert("speed".into(), json!(speed));
+        }
+        Module::Memory | Module::Disk => bytes(&mut map),
+        Module::Gpu => {
+            let ram = number(&map, "ram_bytes");
+            map.insert("ram".into(), json!(crate::units::current().bytes(ram)));
+            map.insert("ram_gib".into(), json!(ram / GIB));
+        }
+        Module::Display => {
+            let resolution = map.get("resolution").and_then(Value::as_array).cloned();
+            let (w, h) = match resolution.as_deref() {
+                Some([w, h]) => (w.clone(), h.clone()),
+                _ => (Value::Null, Value::Null),
+            };
+            map.insert("width".into(), w);
+            map.insert("height".into(), h);
+        }
+        Module::Temperature => {
+            let celsius = number(&map, "temperature_celsius");
+            let (value, unit) = crate::units::current().convert_temperature(celsius);
+            map.insert("temperature".into(), json!(value));
+            map.insert("unit".into(), json!(unit));
+        }
+        Module::Packages => {
+            let total: f64 = map.values().filter_map(Value::as_f64).sum();
+            map.insert("total".into(), json!(total as u64));
+        }
+        _ => {}
+    }
+    map.insert("value".into(), json!(default));
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_template_parse() {
+        assert!("{name} ({cores})".parse::<Template>().is_ok());
+        assert!("{{literal}}".parse::<Template>().is_ok());
+        assert!("{name".parse::<Template>().is_err());
+        assert!("name}".parse::<Template>().is_err());
+        assert!("{ghz:.x}".parse::<Template>().is_err());
+        assert!("{a-b}".parse::<Template>().is_err());
+
+        let cpu: Template = "{sockets}".parse().unwrap();
+        assert!(validate(Module::Cpu, &cpu).is_err());
+        let kernel: Template = "Linux {value}".parse().unwrap();
+        assert!(validate(Module::Kernel, &kernel).is_ok());
+    }
+
+    #[test]
+    fn test_template_render() {
+        let cpu = json!({ "name": "Ryzen 7", "threads": 16, "max_speed_mhz": 5150 });
+        let context = context(Module::Cpu, cpu, String::new());
+        let template: Template = "{name} [{threads}T] @ {max_ghz:.1}GHz".parse().unwrap();
+        assert_eq!(template.render(&context), "Ryzen 7 [16T] @ 5.2GHz");
+
+        let memory = json!({ "total_bytes": 16u64 << 30, "used_bytes": 6u64 << 29 });
+        let context = super::context(Module::Memory, memory, String::new());
+        let template: Template = "{used_gib:.1}/{total_gib:.0} GiB|{percent:>4.0}%|{{x}}"
+            .parse()
+            .unwrap();
+        assert_eq!(template.render(&context), "3.0/16 GiB|  19%|{x}");
+    }
+
+    #[test]
+    fn test_fields_match_context() {
+        use crate::cpu::Cpu;
+        use crate::disk::Disk;
+        use crate::display::Display;
+        use crate::gpu::Gpu;
+        use crate::memory::Memory;
+        use crate::network::NetworkInfo;
+        use crate::os::{Distro, OS};
+        use crate::packages::Packages;
+        use crate::system::LoadAverage;
+        use crate::temperature::TempSensor;
+
+        fn check(module: Module, item: impl serde::Serialize) {
+            let item = serde_json::to_value(item).unwrap();
+            let context = context(module, item, String::new());
+            let mut keys: Vec<_> = context.keys().filter(|k| *k != "value").collect();
+            let mut fields = fields(module).unwrap().to_vec();
+            keys.sort();
+            fields.sort();
+            assert_eq!(keys, fields, "fields of {module}");
+        }
+
+        let os = OS {
+            distro: Distro::Debian,
+            name: "Debian".into(),
+            arch: "x86_64".into(),
+        };
+        check(Module::Os, os);
+        check(Module::Uptime, 3600);
+        let load = LoadAverage {
+            one_min: 0.5,
+            five_min: 0.4,
+            fifteen_min: 0.3,
+        };
+        check(Module::LoadAverage, load);
+        check(Module::Packages, Packages::default());
+        let display = Display {
+            name: None,
+            friendly_name: None,
+            refresh_rate: Some(60.0),
+            external: None,
+            resolution: Some((1920, 1080)),
+            scale_resolution: None,
+            rotation: None,
+            primary: Some(true),
+        };
+        check(Module::Display, display);
+        check(Module::Memory, Memory { total: 2, used: 1 });
+        let disk = Disk {
+            name: "/".into(),
+            total: 2,
+            used: 1,
+        };
+        check(Module::Disk, disk);
+        let cpu = Cpu {
+            name: "Ryzen 7".into(),
+            cores: 8,
+            threads: 16,
+            speed: 3800,
+            max_speed: 5150,
+        };
+        check(Module::Cpu, cpu);
+        let gpu = Gpu {
+            name: "Radeon".into(),
+            version: String::new(),
+            ram: 0,
+        };
+        check(Module::Gpu, gpu);
+        check(Module::Battery, 80);
+        let sensor = TempSensor {
+            label: "cpu".into(),
+            temperature_celsius: 50.0,
+        };
+        check(Module::Temperature, sensor);
+        let interface = NetworkInfo {
+            interface_name: "eth0".into(),
+            ipv4_address: None,
+            ipv6_address: None,
+            mac_address: None,
+            is_up: true,
+        };
+        check(Module::Network, interface);
+    }
+
+    #[test]
+    fn test_format_config() {
+        let config = Config::parse("format.cpu = '{name} @ {ghz:.1}GHz'").unwrap();
+        assert!(config.template(Module::Cpu).is_some());
+        assert!(Config::parse("format.cpu = '{sockets}'").is_err());
+        assert!(Config::parse("format.nope = '{value}'").is_err());
+    }
+}
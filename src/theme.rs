@@ -0,0 +1,275 @@
+//! Colors adapted to the terminal background
+//!
+//! The background is queried with OSC 11 (`ESC ] 11 ; ? BEL`), followed by a
+//! device attributes request that every terminal answers, so terminals without
+//! OSC 11 support are detected immediately instead of waiting for the timeout.
+//! `COLORFGBG` (set by rxvt, Konsole and others) is the fallback.
+//!
+//! On a light background white and bright yellow or cyan text becomes dark; on
+//! a dark background black text becomes gray. The palette blocks are left as-is.
+
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// How long to wait for the terminal to answer
+pub const QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Theme selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Detect the terminal background
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Mode::Auto),
+            "light" => Ok(Mode::Light),
+            "dark" => Ok(Mode::Dark),
+            _ => Err(format!("unknown theme '{s}' (auto, light, dark)")),
+        }
+    }
+}
+
+/// Terminal background brightness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+static BACKGROUND: RwLock<Option<Background>> = RwLock::new(None);
+
+/// Background the colors are adapted to; `None` keeps them unchanged
+pub fn current() -> Option<Background> {
+    BACKGROUND.read().ok().and_then(|b| *b)
+}
+
+/// Set the background the colors are adapted to
+pub fn set(background: Option<Background>) {
+    if let Ok(mut current) = BACKGROUND.write() {
+        *current = background;
+    }
+}
+
+/// Background of a mode, querying the terminal for [`Mode::Auto`]
+pub fn resolve(mode: Mode) -> Option<Background> {
+    match mode {
+        Mode::Light => Some(Background::Light),
+        Mode::Dark => Some(Background::Dark),
+        Mode::Auto => detect(QUERY_TIMEOUT),
+    }
+}
+
+/// Detect the terminal background, `None` when it cannot be told
+pub fn detect(timeout: Duration) -> Option<Background> {
+    query_background(timeout)
+        .as_deref()
+        .and_then(parse_osc11)
+        .or_else(|| parse_colorfgbg(&std::env::var("COLORFGBG").ok()?))
+}
+
+/// Raw answer of the terminal to an OSC 11 and a device attributes query
+#[cfg(unix)]
+fn query_background(timeout: Duration) -> Option<String> {
+    use std::io::{Read, Write};
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let fd = tty.as_raw_fd();
+    let mut original: libc::termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+        return None;
+    }
+    // Read the answer byte by byte without echoing it
+    let mut raw = original;
+    raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+    raw.c_cc[libc::VMIN] = 0;
+    raw.c_cc[libc::VTIME] = 0;
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+        return None;
+    }
+
+    let mut answer = Vec::new();
+    let deadline = Instant::now() + timeout;
+    if tty.write_all(b"\x1b]11;?\x07\x1b[c").is_ok() && tty.flush().is_ok() {
+        // Stop at the device attributes answer (`ESC [ ? ... c`), which comes last
+        while !answer.ends_with(b"c") || !answer.windows(3).any(|w| w == b"\x1b[?") {
+            let Some(left) = deadline.checked_duration_since(Instant::now()) else {
+                break;
+            };
+            let mut poll = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut poll, 1, left.as_millis() as libc::c_int) } <= 0 {
+                break;
+            }
+            let mut chunk = [0u8; 64];
+            match tty.read(&mut chunk) {
+                Ok(n) if n > 0 => answer.extend_from_slice(&chunk[..n]),
+                _ => break,
+            }
+        }
+    }
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+    String::from_utf8(answer).ok()
+}
+
+#[cfg(not(unix))]
+fn query_background(_timeout: Duration) -> Option<String> {
+    None
+}
+
+/// Brightness from an answer such as `ESC ] 11 ; rgb:ffff/ffff/ffff BEL`
+fn parse_osc11(answer: &str) -> Option<Background> {
+    let start = answer.find("]11;")? + 4;
+    let spec = &answer[start..];
+    let spec = spec
+        .strip_prefix("rgb:")
+        .or_else(|| spec.strip_prefix("rgba:"))?;
+    let end = spec.find(['\x07', '\x1b']).unwrap_or(spec.len());
+    // Channels have 1 to 4 hex digits
+    let channel = |hex: &str| {
+        let max = (1u32 << (4 * hex.len() as u32)) - 1;
+        Some(u32::from_str_radix(hex, 16).ok()? as f64 / max as f64)
+    };
+    let mut channels = spec[..end].split('/');
+    let (r, g, b) = (
+        channel(channels.next()?)?,
+        channel(channels.next()?)?,
+        channel(channels.next()?)?,
+    );
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(match luminance > 0.5 {
+        true => Background::Light,
+        false => Background::Dark,
+    })
+}
+
+/// Brightness from `COLORFGBG`, e.g. `15;0` (white on black) or `0;default;15`
+fn parse_colorfgbg(value: &str) -> Option<Background> {
+    let bg: u8 = value.rsplit(';').next()?.parse().ok()?;
+    Some(match bg {
+        7 | 9..=15 => Background::Light,
+        _ => Background::Dark,
+    })
+}
+
+/// Foreground code readable on `background`
+fn readable(code: u32, background: Background) -> u32 {
+    match (background, code) {
+        (Background::Light, 37) => 30,
+        (Background::Light, 97) => 90,
+        (Background::Light, 93) => 33,
+        (Background::Light, 96) => 36,
+        (Background::Dark, 30) => 90,
+        (_, code) => code,
+    }
+}
+
+/// `text` with its foreground colors adapted to the [`current`] background
+pub fn adapt(text: &str) -> String {
+    match current() {
+        Some(background) => adapt_to(text, background),
+        None => text.to_string(),
+    }
+}
+
+fn adapt_to(text: &str, background: Background) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(|c: char| !(c.is_ascii_digit() || c == ';')) else {
+            // Unterminated escape: kept as it is
+            rest = &rest[start..];
+            break;
+        };
+        let (params, tail) = after.split_at(end);
+        out.push_str("\x1b[");
+        if tail.starts_with('m') {
+            let mut codes = params.split(';');
+            let mut mapped = Vec::new();
+            while let Some(code) = codes.next() {
+                match code.parse::<u32>() {
+                    // Extended colors carry their own parameters
+                    Ok(38 | 48) => {
+                        mapped.push(code.to_string());
+                        let kind = codes.next().unwrap_or_default();
+                        mapped.push(kind.to_string());
+                        let n = if kind == "2" { 3 } else { 1 };
+                        mapped.extend(codes.by_ref().take(n).map(str::to_string));
+                    }
+                    Ok(code) => mapped.push(readable(code, background).to_string()),
+                    Err(_) => mapped.push(code.to_string()),
+                }
+            }
+            out.push_str(&mapped.join(";"));
+        } else {
+            out.push_str(params);
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_detection() {
+        let light = "\x1b]11;rgb:ffff/fafa/f0f0\x07\x1b[?62;22c";
+        assert_eq!(parse_osc11(light), Some(Background::Light));
+        let dark = "\x1b]11;rgb:1e/1e/2e\x1b\\";
+        assert_eq!(parse_osc11(dark), Some(Background::Dark));
+        assert_eq!(parse_osc11("\x1b[?62;22c"), None);
+
+        assert_eq!(parse_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("0;default;15"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("default"), None);
+    }
+
+    #[test]
+    fn test_adapt() {
+        let text = "\x1b[37mlogo\x1b[1;97mbold\x1b[38;5;37mx\x1b[0m";
+        assert_eq!(
+            adapt_to(text, Background::Light),
+            "\x1b[30mlogo\x1b[1;90mbold\x1b[38;5;37mx\x1b[0m"
+        );
+        assert_eq!(
+            adapt_to("\x1b[30m#\x1b[5A", Background::Dark),
+            "\x1b[90m#\x1b[5A"
+        );
+        assert_eq!(
+            adapt_to("\x1b[37mlogo\x1b[12", Background::Light),
+            "\x1b[30mlogo\x1b[12"
+        );
+    }
+
+    #[test]
+    fn test_theme_config() {
+        assert_eq!(Config::parse("theme = 'light'").unwrap().theme, Mode::Light);
+        assert!(Config::parse("theme = 'sepia'").is_err());
+    }
+}
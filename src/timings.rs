@@ -0,0 +1,146 @@
+//! Per-module timing report
+//!
+//! Records the wall-clock time of every collector and of every subprocess they
+//! spawn, so slow modules on a given machine are easy to spot.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Neofetch;
+use crate::color::{BOLD, GREEN, RESET};
+use crate::error::Result;
+use crate::module::Module;
+use crate::output::{Format, prepare, serialize};
+use crate::utils::timing::{start_recording, take_recorded};
+
+/// Milliseconds with microsecond precision
+fn millis(elapsed: Duration) -> f64 {
+    elapsed.as_micros() as f64 / 1000.0
+}
+
+/// Wall-clock time of one collector or command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub name: String,
+    pub duration_ms: f64,
+}
+
+impl Timing {
+    fn new(name: impl Into<String>, elapsed: Duration) -> Self {
+        Timing {
+            name: name.into(),
+            duration_ms: millis(elapsed),
+        }
+    }
+}
+
+/// Timings of a full collection, slowest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub total_ms: f64,
+    pub modules: Vec<Timing>,
+    pub commands: Vec<Timing>,
+}
+
+/// Snapshot with its timings appended, for structured output
+#[derive(Serialize)]
+struct Timed<'a> {
+    #[serde(flatten)]
+    neofetch: &'a Neofetch,
+    timings: &'a Timings,
+}
+
+fn sort(timings: &mut [Timing]) {
+    timings.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+}
+
+impl Timings {
+    /// Collect all modules, timing each collector and subprocess
+    pub async fn collect() -> (Neofetch, Timings) {
+        let mut neofetch = Neofetch::empty();
+        start_recording();
+        let start = Instant::now();
+        let modules = neofetch.refresh_timed(&Module::all()).await;
+        let total = start.elapsed();
+        let commands = take_recorded().commands;
+
+        let mut timings = Timings {
+            total_ms: millis(total),
+            modules: modules
+                .into_iter()
+                .map(|(module, elapsed)| Timing::new(module.name(), elapsed))
+                .collect(),
+            commands: commands
+                .into_iter()
+                .map(|(command, elapsed)| Timing::new(command, elapsed))
+                .collect(),
+        };
+        sort(&mut timings.modules);
+        sort(&mut timings.commands);
+        (neofetch, timings)
+    }
+
+    /// Aligned table of modules and commands
+    pub fn to_text(&self) -> String {
+        let width = self
+            .modules
+            .iter()
+            .chain(&self.commands)
+            .map(|t| t.name.len())
+            .max()
+            .unwrap_or(0);
+        let line = |t: &Timing| format!("  {:<width$}  {:>9.2} ms", t.name, t.duration_ms);
+
+        let mut lines = vec![format!("{GREEN}{BOLD}Modules{RESET}")];
+        lines.extend(self.modules.iter().map(line));
+        if !self.commands.is_empty() {
+            lines.push(format!("{GREEN}{BOLD}Commands{RESET}"));
+            lines.extend(self.commands.iter().map(line));
+        }
+        lines.push(format!(
+            "{GREEN}{BOLD}Total: {RESET}{:.2} ms",
+            self.total_ms
+        ));
+        lines.join("\n")
+    }
+
+    /// Render a snapshot followed by its timings
+    pub fn render(&self, neofetch: &Neofetch, format: Format) -> Result<String> {
+        let neofetch = &*prepare(neofetch);
+        match format {
+            Format::Text => Ok(format!("{neofetch}\n{}", self.to_text())),
+            _ => serialize(
+                &Timed {
+                    neofetch,
+                    timings: self,
+                },
+                format,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_text() {
+        let mut modules = vec![
+            Timing::new("kernel", Duration::from_micros(50)),
+            Timing::new("packages", Duration::from_millis(12)),
+        ];
+        sort(&mut modules);
+        let timings = Timings {
+            total_ms: 12.5,
+            modules,
+            commands: vec![Timing::new("opkg list-installed", Duration::from_millis(3))],
+        };
+        let text = timings.to_text();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines[1], "  packages                 12.00 ms");
+        assert_eq!(lines[2], "  kernel                    0.05 ms");
+        assert_eq!(lines[4], "  opkg list-installed       3.00 ms");
+    }
+}
@@ -0,0 +1,295 @@
+//! Units of displayed sizes, temperatures and clock speeds
+//!
+//! Values are collected and serialized in base units (`*_bytes`, `*_celsius`,
+//! `*_mhz`) so structured output stays stable; the [`Units`] set with [`set`]
+//! only decide how they are shown: in the text, HTML and report output, in
+//! `diff`, and in the derived template fields.
+
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use serde::Deserialize;
+
+/// Prefix system of byte sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ByteSystem {
+    /// Powers of 1024: KiB, MiB, GiB, ...
+    #[default]
+    Iec,
+    /// Powers of 1000: KB, MB, GB, ...
+    Si,
+}
+
+impl ByteSystem {
+    fn base(self) -> f64 {
+        match self {
+            ByteSystem::Iec => 1024.0,
+            ByteSystem::Si => 1000.0,
+        }
+    }
+
+    fn suffixes(self) -> [&'static str; 7] {
+        match self {
+            ByteSystem::Iec => ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"],
+            ByteSystem::Si => ["B", "KB", "MB", "GB", "TB", "PB", "EB"],
+        }
+    }
+}
+
+/// A fixed byte unit such as `GiB` or `MB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ByteUnit {
+    system: ByteSystem,
+    power: i32,
+}
+
+impl FromStr for ByteUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for system in [ByteSystem::Iec, ByteSystem::Si] {
+            if let Some(power) = system
+                .suffixes()
+                .iter()
+                .position(|u| u.eq_ignore_ascii_case(s))
+            {
+                return Ok(ByteUnit {
+                    system,
+                    power: power as i32,
+                });
+            }
+        }
+        Err(format!("unknown byte unit '{s}' (e.g. MB, GB, MiB, GiB)"))
+    }
+}
+
+/// Parse a size such as `16G`, `512 MiB` or `1.5TB` into bytes
+///
+/// A single-letter suffix is decimal like `GB`, so `16G` holds for a machine
+/// sold with 16 GB even though it reports less than 16 GiB.
+pub fn parse_bytes(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size '{s}' (e.g. 16G, 512MiB)"))?;
+    let unit: ByteUnit = match unit.trim() {
+        "" => return Ok(number),
+        u if u.len() == 1 && !u.eq_ignore_ascii_case("b") => format!("{u}B").parse()?,
+        u => u.parse()?,
+    };
+    Ok(number * unit.system.base().powi(unit.power))
+}
+
+impl TryFrom<String> for ByteUnit {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Temperature scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    #[serde(alias = "c")]
+    Celsius,
+    #[serde(alias = "f")]
+    Fahrenheit,
+    #[serde(alias = "k")]
+    Kelvin,
+}
+
+/// Clock speed unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum FrequencyUnit {
+    #[serde(rename = "mhz", alias = "MHz")]
+    Mhz,
+    #[default]
+    #[serde(rename = "ghz", alias = "GHz")]
+    Ghz,
+}
+
+/// How sizes, temperatures and clock speeds are displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Units {
+    /// Prefix system of sizes when no unit is forced
+    pub bytes: ByteSystem,
+    /// Show every size in this unit
+    pub byte_unit: Option<ByteUnit>,
+    /// Decimal places of every value; by default 1 for sizes (dropping `.0`)
+    /// and temperatures, 2 for GHz and 0 for MHz
+    pub precision: Option<usize>,
+    pub temperature: TemperatureUnit,
+    pub frequency: FrequencyUnit,
+}
+
+static UNITS: RwLock<Units> = RwLock::new(Units {
+    bytes: ByteSystem::Iec,
+    byte_unit: None,
+    precision: None,
+    temperature: TemperatureUnit::Celsius,
+    frequency: FrequencyUnit::Ghz,
+});
+
+/// Units used by the `Display` implementations
+pub fn current() -> Units {
+    UNITS.read().map(|u| *u).unwrap_or_default()
+}
+
+/// Change the units used by the `Display` implementations
+pub fn set(units: Units) {
+    if let Ok(mut current) = UNITS.write() {
+        *current = units;
+    }
+}
+
+impl Units {
+    fn number(&self, n: f64, default_precision: usize) -> String {
+        let s = match self.precision {
+            Some(p) => format!("{n:.p$}"),
+            None => format!("{n:.default_precision$}"),
+        };
+        crate::i18n::current().decimal(s)
+    }
+
+    /// A size such as `15.6 GiB`
+    pub fn bytes(&self, bytes: f64) -> String {
+        let (system, power) = match self.byte_unit {
+            Some(unit) => (unit.system, unit.power),
+            None if bytes <= 0.0 => (self.bytes, 0),
+            None => {
+                let base = self.bytes.base();
+                let power = (bytes.log(base).floor() as i32).clamp(0, 6);
+                // Rounding may carry into the next unit (999.96 KB -> 1000.0 KB)
+                let scaled = bytes / base.powi(power);
+                match format!("{scaled:.1}").parse::<f64>() {
+                    Ok(rounded) if rounded >= base && power < 6 => (self.bytes, power + 1),
+                    _ => (self.bytes, power),
+                }
+            }
+        };
+        let value = bytes / system.base().powi(power);
+        let number = match self.precision {
+            Some(p) => format!("{value:.p$}"),
+            None => {
+                let s = format!("{value:.1}");
+                s.strip_suffix(".0").map(str::to_string).unwrap_or(s)
+            }
+        };
+        let number = crate::i18n::current().decimal(number);
+        format!("{number} {}", system.suffixes()[power as usize])
+    }
+
+    /// A temperature given in °C as a number and symbol of the configured scale
+    pub fn convert_temperature(&self, celsius: f64) -> (f64, &'static str) {
+        match self.temperature {
+            TemperatureUnit::Celsius => (celsius, "°C"),
+            TemperatureUnit::Fahrenheit => (celsius * 1.8 + 32.0, "°F"),
+            TemperatureUnit::Kelvin => (celsius + 273.15, "K"),
+        }
+    }
+
+    /// A temperature given in °C, converted to the configured scale
+    pub fn temperature(&self, celsius: f64) -> String {
+        let (value, unit) = self.convert_temperature(celsius);
+        let number = self.number(value, 1);
+        match self.temperature {
+            TemperatureUnit::Kelvin => format!("{number} {unit}"),
+            _ => format!("{number}{unit}"),
+        }
+    }
+
+    /// A temperature difference given in °C
+    pub fn temperature_difference(&self, celsius: f64) -> String {
+        let (value, unit) = match self.temperature {
+            TemperatureUnit::Celsius => (celsius, "°C"),
+            TemperatureUnit::Fahrenheit => (celsius * 1.8, "°F"),
+            TemperatureUnit::Kelvin => (celsius, " K"),
+        };
+        format!("{}{unit}", self.number(value, 1))
+    }
+
+    /// A clock speed given in MHz
+    pub fn frequency(&self, mhz: f64) -> String {
+        match self.frequency {
+            FrequencyUnit::Mhz => format!("{} MHz", self.number(mhz, 0)),
+            FrequencyUnit::Ghz => format!("{} GHz", self.number(mhz / 1000.0, 2)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "cli")]
+    use crate::config::Config;
+
+    #[test]
+    fn test_bytes() {
+        let iec = Units::default();
+        assert_eq!(iec.bytes(0.0), "0 B");
+        assert_eq!(iec.bytes(1536.0), "1.5 KiB");
+        assert_eq!(iec.bytes(16.0 * 1024.0 * 1024.0 * 1024.0), "16 GiB");
+        assert_eq!(iec.bytes(1_048_570.0), "1 MiB");
+
+        let si = Units {
+            bytes: ByteSystem::Si,
+            ..Default::default()
+        };
+        assert_eq!(si.bytes(16e9), "16 GB");
+
+        let forced = Units {
+            byte_unit: Some("mib".parse().unwrap()),
+            precision: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(forced.bytes(3.0 * 1024.0 * 1024.0 * 1024.0), "3072.00 MiB");
+        assert!("GiBs".parse::<ByteUnit>().is_err());
+
+        assert_eq!(parse_bytes("16G"), Ok(16e9));
+        assert_eq!(parse_bytes("1.5 KiB"), Ok(1536.0));
+        assert_eq!(parse_bytes("512"), Ok(512.0));
+        assert!(parse_bytes("16 gallons").is_err());
+    }
+
+    #[test]
+    fn test_temperature_and_frequency() {
+        let units = Units {
+            temperature: TemperatureUnit::Fahrenheit,
+            frequency: FrequencyUnit::Mhz,
+            ..Default::default()
+        };
+        assert_eq!(units.temperature(37.0), "98.6°F");
+        assert_eq!(units.temperature_difference(-2.5), "-4.5°F");
+        assert_eq!(units.frequency(4250.0), "4250 MHz");
+
+        let kelvin = Units {
+            temperature: TemperatureUnit::Kelvin,
+            precision: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(kelvin.temperature(26.85), "300 K");
+        assert_eq!(Units::default().frequency(4250.0), "4.25 GHz");
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_units_config() {
+        let config =
+            Config::parse("units = { bytes = 'iec', byte_unit = 'GiB', temperature = 'f' }");
+        assert_eq!(
+            config.unwrap().units.temperature,
+            TemperatureUnit::Fahrenheit
+        );
+        assert!(Config::parse("units.byte_unit = 'GiBs'").is_err());
+    }
+}
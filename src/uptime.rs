@@ -1,13 +1,15 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 const ONE_MINUTE: u64 = 60;
 const ONE_HOUR: u64 = 60 * 60;
 const ONE_DAY: u64 = 60 * 60 * 24;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Time(pub u64);
 
 impl Display for Time {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sep = crate::i18n::current().separator();
         if self.0 < ONE_MINUTE {
             return f.write_str(&with_unit(self.0, "sec"));
         }
@@ -27,7 +29,7 @@ impl Display for Time {
             let min = (self.0 - hour * ONE_HOUR) / ONE_MINUTE;
             let sec = self.0 - hour * ONE_HOUR - min * ONE_MINUTE;
             return f.write_str(&format!(
-                "{}, {}, {}",
+                "{}{sep}{}{sep}{}",
                 with_unit(hour, "hour"),
                 with_unit(min, "min"),
                 with_unit(sec, "sec")
@@ -37,7 +39,7 @@ impl Display for Time {
         let hour = (self.0 - day * ONE_DAY) / ONE_HOUR;
         let min = (self.0 - day * ONE_DAY - hour * ONE_HOUR) / ONE_MINUTE;
         f.write_str(&format!(
-            "{}, {}, {}",
+            "{}{sep}{}{sep}{}",
             with_unit(day, "day"),
             with_unit(hour, "hour"),
             with_unit(min, "min")
@@ -45,8 +47,9 @@ impl Display for Time {
     }
 }
 
+/// `n` with a time unit in the current language
 fn with_unit(n: u64, unit: &str) -> String {
-    format!("{n} {unit}{}", if n > 1 { "s" } else { "" })
+    crate::i18n::current().unit(n, unit)
 }
 
 #[cfg(windows)]
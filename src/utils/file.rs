@@ -4,6 +4,7 @@
 //! and parsing common file formats like /proc files.
 
 use crate::error::{NeofetchError, Result};
+use crate::utils::timing;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -26,6 +27,7 @@ use std::path::Path;
 /// ```
 pub async fn read_file_to_string(path: impl AsRef<Path>) -> Result<String> {
     let path_ref = path.as_ref();
+    timing::record_file(path_ref);
     tokio::fs::read_to_string(path_ref)
         .await
         .map_err(|e| NeofetchError::file_read(path_ref.display().to_string(), e))
@@ -50,6 +52,7 @@ pub async fn read_file_to_string(path: impl AsRef<Path>) -> Result<String> {
 /// ```
 pub fn read_file_to_string_sync(path: impl AsRef<Path>) -> Result<String> {
     let path_ref = path.as_ref();
+    timing::record_file(path_ref);
     std::fs::read_to_string(path_ref)
         .map_err(|e| NeofetchError::file_read(path_ref.display().to_string(), e))
 }
@@ -144,11 +147,26 @@ pub fn get_filename(path: &str) -> Option<String> {
 /// * `Result<usize>` - Number of entries in directory
 pub fn count_dir_entries(path: impl AsRef<Path>) -> Result<usize> {
     let path_ref = path.as_ref();
+    timing::record_file(path_ref);
     std::fs::read_dir(path_ref)
         .map_err(|e| NeofetchError::file_read(path_ref.display().to_string(), e))
         .map(|entries| entries.count())
 }
 
+/// Read directory entries
+///
+/// # Arguments
+/// * `path` - Directory path
+///
+/// # Returns
+/// * `Result<std::fs::ReadDir>` - Iterator over the entries in directory
+pub fn read_dir(path: impl AsRef<Path>) -> Result<std::fs::ReadDir> {
+    let path_ref = path.as_ref();
+    timing::record_file(path_ref);
+    std::fs::read_dir(path_ref)
+        .map_err(|e| NeofetchError::file_read(path_ref.display().to_string(), e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,8 +4,10 @@
 //! and output parsing.
 
 use crate::error::{NeofetchError, Result};
+use crate::utils::timing;
 use std::ffi::OsStr;
 use std::process::Stdio;
+use std::time::Instant;
 
 /// Execute a command asynchronously and return its output
 ///
@@ -30,14 +32,18 @@ pub async fn execute_command(cmd: impl AsRef<OsStr>, args: &[impl AsRef<OsStr>])
     let cmd_ref = cmd.as_ref();
     let cmd_str = cmd_ref.to_string_lossy().to_string();
 
+    let start = Instant::now();
     let output = tokio::process::Command::new(cmd_ref)
         .args(args)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
+        // Callers may wrap this in a timeout; dropping the future must not leak the child
+        .kill_on_drop(true)
         .output()
-        .await
-        .map_err(|e| NeofetchError::command_execution(cmd_str.clone(), e))?;
+        .await;
+    timing::record(cmd_ref, args, start.elapsed());
+    let output = output.map_err(|e| NeofetchError::command_execution(cmd_str.clone(), e))?;
 
     if !output.status.success()
         && let Some(code) = output.status.code()
@@ -71,13 +77,15 @@ pub fn execute_command_sync(cmd: impl AsRef<OsStr>, args: &[impl AsRef<OsStr>])
     let cmd_ref = cmd.as_ref();
     let cmd_str = cmd_ref.to_string_lossy().to_string();
 
+    let start = Instant::now();
     let output = std::process::Command::new(cmd_ref)
         .args(args)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
-        .output()
-        .map_err(|e| NeofetchError::command_execution(cmd_str.clone(), e))?;
+        .output();
+    timing::record(cmd_ref, args, start.elapsed());
+    let output = output.map_err(|e| NeofetchError::command_execution(cmd_str.clone(), e))?;
 
     if !output.status.success()
         && let Some(code) = output.status.code()
@@ -0,0 +1,60 @@
+//! Subprocess timing and file access
+//!
+//! While recording is enabled with [`start_recording`], every command run through
+//! the [`process`](super::process) helpers is recorded with its wall-clock time,
+//! so slow external tools (e.g. `opkg list-installed`) can be spotted, and every
+//! file read through the [`file`](super::file) helpers is recorded by path.
+
+use std::ffi::OsStr;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Commands run and files read since [`start_recording`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recorded {
+    pub commands: Vec<(String, Duration)>,
+    pub files: Vec<String>,
+}
+
+/// Records; `None` while recording is disabled
+static RECORDED: Mutex<Option<Recorded>> = Mutex::new(None);
+
+/// Start recording commands and files, discarding earlier records
+pub fn start_recording() {
+    if let Ok(mut recorded) = RECORDED.lock() {
+        *recorded = Some(Recorded::default());
+    }
+}
+
+/// Stop recording and return what was recorded since [`start_recording`]
+pub fn take_recorded() -> Recorded {
+    RECORDED
+        .lock()
+        .ok()
+        .and_then(|mut recorded| recorded.take())
+        .unwrap_or_default()
+}
+
+/// Record one finished command if recording is enabled
+pub(crate) fn record(cmd: &OsStr, args: &[impl AsRef<OsStr>], elapsed: Duration) {
+    if let Ok(mut recorded) = RECORDED.lock()
+        && let Some(recorded) = recorded.as_mut()
+    {
+        let mut line = cmd.to_string_lossy().into_owned();
+        for arg in args {
+            line.push(' ');
+            line.push_str(&arg.as_ref().to_string_lossy());
+        }
+        recorded.commands.push((line, elapsed));
+    }
+}
+
+/// Record one file or directory read if recording is enabled
+pub(crate) fn record_file(path: &Path) {
+    if let Ok(mut recorded) = RECORDED.lock()
+        && let Some(recorded) = recorded.as_mut()
+    {
+        recorded.files.push(path.display().to_string());
+    }
+}
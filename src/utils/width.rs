@@ -0,0 +1,58 @@
+//! Terminal column width of styled text
+//!
+//! ANSI escape sequences take no space; East Asian wide characters and emoji
+//! (including text symbols made emoji by U+FE0F, such as `⚙️`) take two columns.
+//! Nerd Font glyphs live in the Private Use Area and count as one column, as
+//! in the "Mono" variants of the fonts.
+
+use unicode_width::UnicodeWidthStr;
+
+/// Number of terminal columns `s` occupies
+pub fn width(s: &str) -> usize {
+    let mut total = 0;
+    let mut rest = s;
+    while let Some(start) = rest.find('\x1b') {
+        total += rest[..start].width();
+        rest = skip_escape(&rest[start..]);
+    }
+    total + rest.width()
+}
+
+/// Text after the escape sequence at the start of `s`
+fn skip_escape(s: &str) -> &str {
+    let mut chars = s.char_indices().skip(1);
+    match chars.next() {
+        // CSI: parameters up to a final byte in '@'..='~'
+        Some((_, '[')) => match chars.find(|(_, c)| ('@'..='~').contains(c)) {
+            Some((i, c)) => &s[i + c.len_utf8()..],
+            None => "",
+        },
+        // OSC: up to BEL or ST (ESC \)
+        Some((_, ']')) => {
+            let body = &s[2..];
+            match (body.find('\x07'), body.find("\x1b\\")) {
+                (Some(bel), Some(st)) if st < bel => &body[st + 2..],
+                (Some(bel), _) => &body[bel + 1..],
+                (None, Some(st)) => &body[st + 2..],
+                (None, None) => "",
+            }
+        }
+        Some((i, c)) => &s[i + c.len_utf8()..],
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width() {
+        assert_eq!(width("\x1b[1;32mOS\x1b[0m: x"), 5);
+        assert_eq!(width("内存"), 4);
+        assert_eq!(width("🐏 Memory"), 9);
+        assert_eq!(width("⚙️ Kernel"), 9);
+        assert_eq!(width("\u{f035b} Memory"), 8);
+        assert_eq!(width("\x1b]8;;https://x\x1b\\link\x1b]8;;\x07"), 4);
+    }
+}
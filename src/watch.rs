@@ -0,0 +1,136 @@
+//! Live terminal output
+//!
+//! Watch mode collects everything once, then periodically refreshes only the
+//! volatile fields (see [`Module::volatile`]) and redraws the output in place.
+//! Structured formats, and text written to a file or pipe, print one document
+//! per refresh instead.
+//!
+//! Progressive mode draws the first frame as soon as the fastest collector
+//! finishes and fills in the slower modules as they arrive.
+
+use std::borrow::Cow;
+use std::io::{IsTerminal, Write};
+use std::pin::pin;
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::color::{
+    ALT_SCREEN, CLEAR_BELOW, CLEAR_LINE, CURSOR_HOME, HIDE_CURSOR, MAIN_SCREEN, SHOW_CURSOR,
+    cursor_up,
+};
+use crate::error::Result;
+use crate::module::Module;
+use crate::output::{Format, render};
+use crate::{Neofetch, join_padded};
+
+/// Redraw a frame over the previous one, clearing leftovers of longer lines
+fn frame(output: &str) -> String {
+    let mut s = String::new();
+    for line in output.lines() {
+        s.push_str(line);
+        s.push_str(CLEAR_LINE);
+        s.push('\n');
+    }
+    s.push_str(CLEAR_BELOW);
+    s
+}
+
+/// Columns and rows of the terminal on stdout, `None` when it is not one
+#[cfg(unix)]
+pub(crate) fn terminal_size() -> Option<(usize, usize)> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    match unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } {
+        0 if size.ws_col > 0 && size.ws_row > 0 => {
+            Some((size.ws_col as usize, size.ws_row as usize))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn terminal_size() -> Option<(usize, usize)> {
+    None
+}
+
+async fn run(
+    neofetch: &mut Neofetch,
+    interval: Duration,
+    format: Format,
+    in_place: bool,
+) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    loop {
+        let output = render(neofetch, format)?;
+        if in_place {
+            write!(stdout, "{CURSOR_HOME}{}", frame(&output))?;
+        } else {
+            writeln!(stdout, "{output}")?;
+        }
+        stdout.flush()?;
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(interval) => {}
+        }
+        neofetch.refresh(&Module::volatile()).await;
+    }
+}
+
+/// Refresh and print until interrupted with Ctrl-C
+pub async fn watch(mut neofetch: Neofetch, interval: Duration, format: Format) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    if format != Format::Text || !stdout.is_terminal() {
+        return run(&mut neofetch, interval, format, false).await;
+    }
+
+    write!(stdout, "{ALT_SCREEN}{HIDE_CURSOR}")?;
+    let result = run(&mut neofetch, interval, format, true).await;
+    write!(stdout, "{SHOW_CURSOR}{MAIN_SCREEN}")?;
+    stdout.flush()?;
+    result
+}
+
+async fn draw_progressive(neofetch: &mut Neofetch, modules: &[Module]) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    let mut events = pin!(Neofetch::stream(modules));
+    // The cursor cannot move above the screen, so lines of a taller frame that
+    // scrolled out are left behind and the new frame starts at the top
+    let rows = terminal_size().map_or(usize::MAX, |(_, rows)| rows);
+    let mut height = 0;
+    let mut draw = |neofetch: &Neofetch| -> Result<()> {
+        let neofetch = &*crate::redact::apply(neofetch);
+        let output = join_padded(&neofetch.logo(), &neofetch.info());
+        let up = height.min(rows - 1);
+        if up > 0 {
+            write!(stdout, "{}", cursor_up(up))?;
+        }
+        write!(stdout, "\r{}", frame(&output))?;
+        stdout.flush()?;
+        height = output.lines().count();
+        Ok(())
+    };
+    // Modules filled in beforehand (e.g. from the cache) are shown right away
+    if modules.len() < Module::all().len() {
+        draw(neofetch)?;
+    }
+    while let Some((module, value)) = events.next().await {
+        neofetch.set_value(module, value);
+        draw(neofetch)?;
+    }
+    // Scripts see the complete data, so they only apply to the final frame
+    if let Cow::Owned(scripted) = crate::script::apply(neofetch) {
+        draw(&scripted)?;
+    }
+    Ok(())
+}
+
+/// Collect `modules` into `neofetch`, redrawing the text output as each one arrives
+pub async fn progressive(mut neofetch: Neofetch, modules: &[Module]) -> Result<Neofetch> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "{HIDE_CURSOR}")?;
+    let result = draw_progressive(&mut neofetch, modules).await;
+    write!(stdout, "{SHOW_CURSOR}")?;
+    stdout.flush()?;
+    result.map(|_| neofetch)
+}